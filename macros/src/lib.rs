@@ -6,14 +6,16 @@
 
 use parser::UserEnum;
 use proc_macro::TokenStream;
+use set::ErrorSets;
 use syn::{parse_macro_input, DeriveInput};
 
 pub(crate) mod parser;
+pub(crate) mod set;
 pub(crate) mod traits;
 pub(crate) mod util;
 
 /// Derives `core::error::Error` from special syntax.
-#[proc_macro_derive(Error, attributes(error, from))]
+#[proc_macro_derive(Error, attributes(error, from, source))]
 pub fn derive_error(input: TokenStream) -> TokenStream {
     // all derives must be in the root module...
     let synd_input = parse_macro_input!(input as DeriveInput);
@@ -31,3 +33,17 @@ pub fn derive_error(input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// Makes error enums from sets of variants, where one set can include all of
+/// another's variants. Each set derives `Error`, and a set made from another
+/// can convert to and from it.
+#[proc_macro]
+pub fn error_set(input: TokenStream) -> TokenStream {
+    let sets = parse_macro_input!(input as ErrorSets);
+
+    match sets.expand() {
+        Ok(ts) => ts,
+        Err(e) => e.into_compile_error(),
+    }
+    .into()
+}
@@ -1,20 +1,508 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::{meta::ParseNestedMeta, Attribute, Ident, LitInt, LitStr, Meta, Token};
 
 use super::field::WrappedFieldInfo;
+use crate::util;
 
 // #[from] contains a name and type. but that's just a field!
 pub(crate) type FromAttribute = WrappedFieldInfo;
 
 /// An attribute that describes a specific error variant.
 ///
-/// Can either look like `#[error("some message here")]` or `#[error(transparent)]`
-/// for variants with the `#[from]` attribute.
+/// Can either look like `#[error("some message here")]`, `#[error(transparent)]`
+/// for variants with the `#[from]` attribute, or `#[error(many)]` for variants
+/// holding a list of errors.
 #[derive(Debug)]
 pub(crate) enum ErrorAttribute {
     Stringy(TokenStream),
     Transparent,
+    Many,
 }
 
 impl ErrorAttribute {
     pub(crate) const TRANSPARENT_LITERAL: &'static str = "transparent";
+    pub(crate) const MANY_LITERAL: &'static str = "many";
+}
+
+/// Options given to a variant, like `#[error(status = 404)]`.
+///
+/// These live in their own `#[error(...)]` attributes, next to the one with
+/// the variant's message.
+#[derive(Debug, Default)]
+pub(crate) struct VariantOptions {
+    /// The HTTP status code for this variant.
+    pub status: Option<u16>,
+    /// A URI identifying this kind of problem.
+    pub type_uri: Option<LitStr>,
+    /// A short summary of this kind of problem.
+    pub title: Option<LitStr>,
+    /// The process exit code for this variant.
+    pub exit_code: Option<u8>,
+    /// The `std::io::ErrorKind` for this variant.
+    pub io_kind: Option<Ident>,
+    /// The raw integer code for this variant, for FFI.
+    pub errno: Option<RawCode>,
+    /// How severe this variant is, as a `pisserror::Level` variant.
+    pub level: Option<Ident>,
+    /// The ID of this variant's message in a localization catalog.
+    pub l10n: Option<LitStr>,
+}
+
+/// A raw integer code, like the `-22` in `#[error(errno = -22)]`.
+///
+/// We don't know the enum's `repr` while parsing a variant, so this holds
+/// any integer. It's checked against the `repr` later.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawCode {
+    pub value: i128,
+    pub span: Span,
+}
+
+impl VariantOptions {
+    /// Checks if the given attribute holds options (`#[error(key = value)]`)
+    /// rather than a message.
+    pub(crate) fn is_options_attribute(attr: &Attribute) -> bool {
+        let Meta::List(ref list) = attr.meta else {
+            return false;
+        };
+
+        let mut tokens = list.tokens.clone().into_iter();
+        matches!(
+            (tokens.next(), tokens.next()),
+            (Some(TokenTree::Ident(_)), Some(TokenTree::Punct(ref p))) if p.as_char() == '='
+        )
+    }
+
+    /// Parses one `#[error(...)]` attribute full of options.
+    pub(crate) fn parse_attribute(&mut self, attr: &Attribute) -> syn::Result<()> {
+        attr.parse_nested_meta(|meta| self.parse_option(&meta))
+    }
+
+    /// Parses one option, like the `status = 404` in `#[error(status = 404)]`.
+    fn parse_option(&mut self, meta: &ParseNestedMeta<'_>) -> syn::Result<()> {
+        if meta.path.is_ident("status") {
+            if self.status.is_some() {
+                return Err(meta.error("The `status` option may only be given once."));
+            }
+
+            self.status = Some(parse_status(meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("exit_code") {
+            if self.exit_code.is_some() {
+                return Err(meta.error("The `exit_code` option may only be given once."));
+            }
+
+            self.exit_code = Some(parse_exit_code(meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("io_kind") {
+            if self.io_kind.is_some() {
+                return Err(meta.error("The `io_kind` option may only be given once."));
+            }
+
+            self.io_kind = Some(parse_io_kind(meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("errno") {
+            if self.errno.is_some() {
+                return Err(meta.error("The `errno` option may only be given once."));
+            }
+
+            let input = meta.value()?;
+            let negative = input.parse::<Option<Token![-]>>()?.is_some();
+            let lit: LitInt = input.parse()?;
+            let value = lit.base10_parse::<i128>()?;
+
+            self.errno = Some(RawCode {
+                value: if negative { -value } else { value },
+                span: lit.span(),
+            });
+            return Ok(());
+        }
+
+        if meta.path.is_ident("level") {
+            if self.level.is_some() {
+                return Err(meta.error("The `level` option may only be given once."));
+            }
+
+            self.level = Some(parse_level(meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("l10n") {
+            if self.l10n.is_some() {
+                return Err(meta.error("The `l10n` option may only be given once."));
+            }
+
+            self.l10n = Some(parse_l10n(meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("type_uri") {
+            if self.type_uri.is_some() {
+                return Err(meta.error("The `type_uri` option may only be given once."));
+            }
+
+            self.type_uri = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("title") {
+            if self.title.is_some() {
+                return Err(meta.error("The `title` option may only be given once."));
+            }
+
+            self.title = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        Err(meta.error("Unknown option for a variant's `#[error(...)]` attribute."))
+    }
+
+    /// Checks if any of the problem details options were given.
+    pub(crate) const fn has_problem_details(&self) -> bool {
+        self.status.is_some() || self.type_uri.is_some() || self.title.is_some()
+    }
+}
+
+/// Parses the value of a `status` option, making sure it's a real HTTP
+/// status code.
+fn parse_status(meta: &ParseNestedMeta<'_>) -> syn::Result<u16> {
+    let lit: LitInt = meta.value()?.parse()?;
+    let status = lit.base10_parse::<u16>()?;
+
+    if !(100..=599).contains(&status) {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "The `status` option must be an HTTP status code, from 100 to 599.",
+        ));
+    }
+
+    Ok(status)
+}
+
+/// Parses the value of an `io_kind` option, like the `NotFound` in
+/// `#[error(io_kind = NotFound)]`.
+///
+/// `std::io::Error` only exists with `std`, so this complains without it.
+fn parse_io_kind(meta: &ParseNestedMeta<'_>) -> syn::Result<Ident> {
+    if !cfg!(feature = "std") {
+        return Err(meta.error(
+            "The `io_kind` option requires pisserror's `std` feature, as \
+            `std::io::Error` isn't available without it.",
+        ));
+    }
+
+    meta.value()?.parse()
+}
+
+/// Parses the value of an `l10n` option, like the `"db-key-not-found"` in
+/// `#[error(l10n = "db-key-not-found")]`.
+///
+/// Localizing needs the `MessageSource` trait, so this complains without the
+/// `i18n` feature.
+fn parse_l10n(meta: &ParseNestedMeta<'_>) -> syn::Result<LitStr> {
+    if !cfg!(feature = "i18n") {
+        return Err(meta.error(
+            "The `l10n` option requires pisserror's `i18n` feature. \
+            Please enable it in your `Cargo.toml`.",
+        ));
+    }
+
+    let id: LitStr = meta.value()?.parse()?;
+    if id.value().is_empty() {
+        return Err(syn::Error::new_spanned(
+            id,
+            "The `l10n` option needs a message ID, like `\"db-key-not-found\"`.",
+        ));
+    }
+
+    Ok(id)
+}
+
+/// Parses the value of a `level` option, like the `warn` in
+/// `#[error(level = warn)]`.
+///
+/// Returns the matching `pisserror::Level` variant, like `Warn`.
+fn parse_level(meta: &ParseNestedMeta<'_>) -> syn::Result<Ident> {
+    let level: Ident = meta.value()?.parse()?;
+
+    let variant =
+        match level.to_string().as_str() {
+            "error" => "Error",
+            "warn" => "Warn",
+            "info" => "Info",
+            "debug" => "Debug",
+            "trace" => "Trace",
+            _ => return Err(syn::Error::new_spanned(
+                level,
+                "The `level` option must be one of `error`, `warn`, `info`, `debug`, or `trace`.",
+            )),
+        };
+
+    Ok(Ident::new(variant, level.span()))
+}
+
+/// Parses the value of an `exit_code` option. Zero means success, so it's not
+/// allowed.
+fn parse_exit_code(meta: &ParseNestedMeta<'_>) -> syn::Result<u8> {
+    let lit: LitInt = meta.value()?.parse()?;
+    let exit_code = lit.base10_parse::<u8>()?;
+
+    if exit_code == 0 {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "The `exit_code` option must be from 1 to 255, as 0 means success.",
+        ));
+    }
+
+    Ok(exit_code)
+}
+
+/// Options given to the enum itself, like `#[error(debug = display)]` or
+/// `#[error(serialize)]`.
+#[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct EnumAttributes {
+    /// How the generated `Debug` implementation should look, if we're making
+    /// one at all.
+    pub debug: Option<DebugStyle>,
+    /// Whether to implement serde's `Serialize`.
+    pub serialize: bool,
+    /// Whether to implement `defmt::Format`.
+    pub defmt: bool,
+    /// Whether to implement `ufmt::uDisplay` and `ufmt::uDebug`.
+    pub ufmt: bool,
+    /// Whether to make a context selector for each variant.
+    pub context_selectors: bool,
+    /// Whether to make a constructor function for each variant.
+    pub constructors: bool,
+    /// Whether to make `is_*`, `as_*`, and `into_*` methods for each variant.
+    pub accessors: bool,
+    /// Whether to make an `ERROR_CATALOG` describing each variant.
+    pub catalog: bool,
+    /// Whether to implement pisserror's `Trace`.
+    pub trace: bool,
+    /// The HTTP status code for variants that don't have their own.
+    pub status: Option<u16>,
+    /// The process exit code for variants that don't have their own.
+    pub exit_code: Option<u8>,
+    /// The `std::io::ErrorKind` for variants that don't have their own.
+    pub io_kind: Option<Ident>,
+    /// The `pisserror::Level` for variants that don't have their own.
+    pub level: Option<Ident>,
+    /// The integer type used for `errno` codes.
+    pub repr: Option<Ident>,
+    /// The name of the generated `extern "C"` function for `errno` messages.
+    pub strerror: Option<Ident>,
+}
+
+/// The kinds of `Debug` implementation we can generate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DebugStyle {
+    /// Print the `Display` message.
+    Display,
+    /// Print the `Display` message, followed by each source.
+    Report,
+}
+
+impl EnumAttributes {
+    /// Parses all the `#[error(...)]` attributes on the enum itself.
+    pub(crate) fn parse(span: Span, attrs: &[Attribute]) -> syn::Result<Self> {
+        let error_attribute_path = util::create_path(span, &["error"]);
+        let mut enum_attributes = Self::default();
+
+        for attr in attrs.iter().filter(|a| a.path() == &error_attribute_path) {
+            attr.parse_nested_meta(|meta| enum_attributes.parse_option(&meta))?;
+        }
+
+        Ok(enum_attributes)
+    }
+
+    /// Parses one option, like the `debug = display` in
+    /// `#[error(debug = display)]`.
+    #[allow(clippy::too_many_lines)] // one block for each option
+    fn parse_option(&mut self, meta: &ParseNestedMeta<'_>) -> syn::Result<()> {
+        if meta.path.is_ident("debug") {
+            if self.debug.is_some() {
+                return Err(meta.error("The `debug` option may only be given once."));
+            }
+
+            let style: Ident = meta.value()?.parse()?;
+            self.debug = Some(match style.to_string().as_str() {
+                "display" => DebugStyle::Display,
+                "report" => DebugStyle::Report,
+                _ => return Err(Self::err_unknown_debug_style(&style)),
+            });
+
+            return Ok(());
+        }
+
+        if meta.path.is_ident("status") {
+            if self.status.is_some() {
+                return Err(meta.error("The `status` option may only be given once."));
+            }
+
+            self.status = Some(parse_status(meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("exit_code") {
+            if self.exit_code.is_some() {
+                return Err(meta.error("The `exit_code` option may only be given once."));
+            }
+
+            self.exit_code = Some(parse_exit_code(meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("io_kind") {
+            if self.io_kind.is_some() {
+                return Err(meta.error("The `io_kind` option may only be given once."));
+            }
+
+            self.io_kind = Some(parse_io_kind(meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("level") {
+            if self.level.is_some() {
+                return Err(meta.error("The `level` option may only be given once."));
+            }
+
+            self.level = Some(parse_level(meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("repr") {
+            if self.repr.is_some() {
+                return Err(meta.error("The `repr` option may only be given once."));
+            }
+
+            let repr: Ident = meta.value()?.parse()?;
+            if Self::repr_range(&repr).is_none() {
+                return Err(syn::Error::new_spanned(
+                    repr,
+                    "The `repr` option must be a primitive integer type, like `i32`.",
+                ));
+            }
+
+            self.repr = Some(repr);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("strerror") {
+            if self.strerror.is_some() {
+                return Err(meta.error("The `strerror` option may only be given once."));
+            }
+
+            self.strerror = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("defmt") {
+            if !cfg!(feature = "defmt") {
+                return Err(meta.error(
+                    "The `defmt` option requires pisserror's `defmt` feature. \
+                    Please enable it in your `Cargo.toml`.",
+                ));
+            }
+
+            self.defmt = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("ufmt") {
+            if !cfg!(feature = "ufmt") {
+                return Err(meta.error(
+                    "The `ufmt` option requires pisserror's `ufmt` feature. \
+                    Please enable it in your `Cargo.toml`.",
+                ));
+            }
+
+            self.ufmt = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("context_selectors") {
+            self.context_selectors = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("constructors") {
+            self.constructors = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("accessors") {
+            self.accessors = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("catalog") {
+            if !cfg!(feature = "catalog") {
+                return Err(meta.error(
+                    "The `catalog` option requires pisserror's `catalog` feature. \
+                    Please enable it in your `Cargo.toml`.",
+                ));
+            }
+
+            self.catalog = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("trace") {
+            if !cfg!(feature = "tracing") {
+                return Err(meta.error(
+                    "The `trace` option requires pisserror's `tracing` feature. \
+                    Please enable it in your `Cargo.toml`.",
+                ));
+            }
+
+            self.trace = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("serialize") {
+            if !cfg!(feature = "serde") {
+                return Err(meta.error(
+                    "The `serialize` option requires pisserror's `serde` feature. \
+                    Please enable it in your `Cargo.toml`.",
+                ));
+            }
+
+            self.serialize = true;
+            return Ok(());
+        }
+
+        Err(meta.error("Unknown option for an enum's `#[error(...)]` attribute."))
+    }
+
+    /// The smallest and largest values of the given integer type, if it is
+    /// one.
+    pub(crate) fn repr_range(repr: &Ident) -> Option<(i128, i128)> {
+        // `isize` and `usize` might only be 32 bits wide
+        Some(match repr.to_string().as_str() {
+            "i8" => (i8::MIN.into(), i8::MAX.into()),
+            "i16" => (i16::MIN.into(), i16::MAX.into()),
+            "i32" | "isize" => (i32::MIN.into(), i32::MAX.into()),
+            "i64" => (i64::MIN.into(), i64::MAX.into()),
+            "u8" => (u8::MIN.into(), u8::MAX.into()),
+            "u16" => (u16::MIN.into(), u16::MAX.into()),
+            "u32" | "usize" => (u32::MIN.into(), u32::MAX.into()),
+            "u64" => (u64::MIN.into(), u64::MAX.into()),
+            _ => return None,
+        })
+    }
+
+    fn err_unknown_debug_style(style: &Ident) -> syn::Error {
+        syn::Error::new_spanned(
+            style,
+            "The `debug` option must be either `display` or `report`.",
+        )
+    }
 }
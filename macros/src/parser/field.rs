@@ -1,5 +1,5 @@
 use proc_macro2::Span;
-use syn::{spanned::Spanned as _, Attribute, Field, Ident, Type};
+use syn::{parse::ParseStream, spanned::Spanned as _, Attribute, Expr, Field, Ident, Token, Type};
 
 use crate::util;
 
@@ -25,6 +25,18 @@ pub(crate) struct WrappedFieldInfo {
     pub ident: Option<Ident>,
     pub ty: Type,
     pub span: Span,
+    /// Whether the field was marked with `#[error(skip_serializing)]`.
+    pub skip_serializing: bool,
+    /// Whether the field was marked with `#[error(skip_l10n)]`.
+    pub skip_l10n: bool,
+    /// Whether the field was marked with `#[source]`.
+    pub source: bool,
+    /// The types listed in `#[from(...)]`, which each go `Into` the field.
+    /// This is empty for a plain `#[from]`.
+    pub from_types: Vec<Type>,
+    /// The conversion given with `#[from(via = ...)]`, which is used instead
+    /// of `Into`.
+    pub from_via: Option<Expr>,
 }
 
 impl PartialEq for WrappedFieldInfo {
@@ -41,6 +53,13 @@ pub(crate) enum WrappedField {
 }
 
 impl WrappedField {
+    /// Information about this field, regardless of its attributes.
+    pub(crate) const fn info(&self) -> &WrappedFieldInfo {
+        match *self {
+            Self::Typical(ref info) | Self::FromAttribute(ref info) => info,
+        }
+    }
+
     /// Checks if this field has the `#[from]` attribute.
     pub(crate) const fn has_from_attribute(&self) -> bool {
         match *self {
@@ -82,7 +101,16 @@ impl FromAttributeSplit {
         } = field;
 
         Self {
-            field_info: WrappedFieldInfo { ident, ty, span },
+            field_info: WrappedFieldInfo {
+                ident,
+                ty,
+                span,
+                skip_serializing: false,
+                skip_l10n: false,
+                source: false,
+                from_types: Vec::new(),
+                from_via: None,
+            },
             attributes: attrs,
         }
     }
@@ -96,43 +124,95 @@ impl FromAttributeCheck {
     /// Checks this field for a `from` attribute. Returns an error if the
     /// `from` rules are violated.
     pub(crate) fn check_from(split: FromAttributeSplit) -> syn::Result<Self> {
-        let (field_info, attrs) = (split.field_info, split.attributes);
-        let field_span = &field_info.span;
+        let (mut field_info, attrs) = (split.field_info, split.attributes);
+        let field_span = field_info.span;
         let from_attribute_path = &util::create_path(field_info.span, &["from"]);
+        let source_attribute_path = &util::create_path(field_info.span, &["source"]);
+        let error_attribute_path = &util::create_path(field_info.span, &["error"]);
 
         let mut already_found_from_attribute = false;
 
         for attr in attrs {
+            if attr.path() == error_attribute_path {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("skip_serializing") {
+                        field_info.skip_serializing = true;
+                        return Ok(());
+                    }
+                    if meta.path.is_ident("skip_l10n") {
+                        field_info.skip_l10n = true;
+                        return Ok(());
+                    }
+
+                    Err(meta.error("Unknown option for a field's `#[error(...)]` attribute."))
+                })?;
+            }
+
+            if attr.path() == source_attribute_path {
+                if field_info.source {
+                    return Err(Self::err_too_many_source_attributes(attr.span()));
+                }
+                if !matches!(attr.meta, syn::Meta::Path(_)) {
+                    return Err(Self::err_source_attribute_has_args(attr.span()));
+                }
+
+                field_info.source = true;
+            }
+
             if attr.path() == from_attribute_path {
                 if already_found_from_attribute {
-                    return Err(Self::err_too_many_from_attributes(*field_span));
+                    return Err(Self::err_too_many_from_attributes(field_span));
                 }
 
-                // check if the attr has some args
+                // `#[from]` may list some types to convert from instead
                 match attr.meta {
-                    syn::Meta::List(_) | syn::Meta::NameValue(_) => {
+                    syn::Meta::List(ref list) => {
+                        list.parse_args_with(|input: ParseStream<'_>| {
+                            Self::parse_from_args(input, &mut field_info)
+                        })?;
+
+                        if field_info.from_types.is_empty() {
+                            // `via` alone converts from whatever the pointer holds
+                            if field_info.from_via.is_none() {
+                                return Err(Self::err_from_attribute_has_args(attr.span()));
+                            }
+                            match util::pointee(&field_info.ty) {
+                                None => return Err(Self::err_via_needs_a_type(attr.span())),
+                                // `dyn Error` isn't sized, so nothing converts from it
+                                Some(pointee) if matches!(*pointee, Type::TraitObject(_)) => {
+                                    return Err(Self::err_via_trait_object(pointee));
+                                }
+                                Some(_) => (),
+                            }
+                        }
+                    }
+                    syn::Meta::NameValue(_) => {
                         return Err(Self::err_from_attribute_has_args(attr.span()))
                     }
-                    syn::Meta::Path(_) => (), // good. there are no args in `#[from]`
+                    syn::Meta::Path(_) => (), // good. the field's own type is used
                 }
 
-                // if there are lifetimes on the from type, complain!
-                if let Type::Reference(ref inner) = field_info.ty {
-                    // but only if it's not static
-                    if let Some(ref lt) = inner.lifetime {
-                        if lt.ident != "static" {
-                            return Err(Self::err_from_attribute_field_has_nonstatic_lifetime(
-                                *field_span,
-                                lt.ident.span(),
-                            ));
-                        }
-                    }
+                // there's no good way to convert into an optional source
+                if util::option_inner(&field_info.ty).is_some() {
+                    return Err(Self::err_from_option(&field_info.ty));
                 }
 
+                // if there are lifetimes on the from type, complain!
+                Self::check_lifetime(&field_info)?;
+
                 already_found_from_attribute = true;
             }
         }
 
+        if already_found_from_attribute && field_info.source {
+            return Err(Self::err_source_and_from(field_span));
+        }
+
+        // sources are borrowed for `'static`, just like `#[from]` fields
+        if field_info.source {
+            Self::check_lifetime(&field_info)?;
+        }
+
         Ok(Self {
             wrapped_field: if already_found_from_attribute {
                 WrappedField::FromAttribute(field_info)
@@ -142,6 +222,81 @@ impl FromAttributeCheck {
         })
     }
 
+    /// Parses the inside of `#[from(...)]`: some types, and maybe a
+    /// `via = ...` conversion.
+    fn parse_from_args(
+        input: ParseStream<'_>,
+        field_info: &mut WrappedFieldInfo,
+    ) -> syn::Result<()> {
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                let key = input.parse::<Ident>()?;
+                if key != "via" {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "Unknown option for the `#[from(...)]` attribute.",
+                    ));
+                }
+                if field_info.from_via.is_some() {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "The `#[from(...)]` attribute may only have one `via`.",
+                    ));
+                }
+
+                input.parse::<Token![=]>()?;
+                field_info.from_via = Some(input.parse()?);
+            } else {
+                field_info.from_types.push(input.parse()?);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the field doesn't borrow anything for less than `'static`.
+    fn check_lifetime(field_info: &WrappedFieldInfo) -> syn::Result<()> {
+        if let Type::Reference(ref inner) = field_info.ty {
+            // but only if it's not static
+            if let Some(ref lt) = inner.lifetime {
+                if lt.ident != "static" {
+                    return Err(Self::err_from_attribute_field_has_nonstatic_lifetime(
+                        field_info.span,
+                        lt.ident.span(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn err_too_many_source_attributes(attribute_span: Span) -> syn::Error {
+        syn::Error::new(
+            attribute_span,
+            "A field may only have one `#[source]` attribute.",
+        )
+    }
+
+    fn err_source_attribute_has_args(attribute_span: Span) -> syn::Error {
+        syn::Error::new(
+            attribute_span,
+            "The `#[source]` attribute does not take any arguments, but some were found.",
+        )
+    }
+
+    fn err_source_and_from(field_span: Span) -> syn::Error {
+        syn::Error::new(
+            field_span,
+            "A `#[from]` field is already the variant's source, so it doesn't need `#[source]`.",
+        )
+    }
+
     pub(crate) fn err_too_many_from_attributes(field_span: Span) -> syn::Error {
         syn::Error::new(
             field_span,
@@ -152,7 +307,32 @@ impl FromAttributeCheck {
     pub(crate) fn err_from_attribute_has_args(attribute_span: Span) -> syn::Error {
         syn::Error::new(
             attribute_span,
-            "The `#[from]` attribute does not take any arguments, but some were found.",
+            "The `#[from]` attribute only takes a list of types to convert from, like \
+            `#[from(ParseIntError, ParseFloatError)]`.",
+        )
+    }
+
+    fn err_from_option(ty: &Type) -> syn::Error {
+        syn::Error::new_spanned(
+            ty,
+            "A `#[from]` field can't be an `Option`. For a source that might not be there, \
+            use `#[source]` instead.",
+        )
+    }
+
+    fn err_via_needs_a_type(attribute_span: Span) -> syn::Error {
+        syn::Error::new(
+            attribute_span,
+            "`via` can only guess the type to convert from when the field is a `Box`, `Arc`, or \
+            `Rc`. Please list it, like `#[from(io::Error, via = Arc::new)]`.",
+        )
+    }
+
+    fn err_via_trait_object(pointee: &Type) -> syn::Error {
+        syn::Error::new_spanned(
+            pointee,
+            "`via` can't convert from a trait object, since it isn't sized. Please list the \
+            types to convert from, like `#[from(io::Error, via = Arc::new)]`.",
         )
     }
 
@@ -0,0 +1,241 @@
+//! # Format
+//!
+//! Splits a variant's `#[error("...")]` message into text and arguments.
+//!
+//! `Display` hands messages straight to `format_args!`, but other formatting
+//! crates (like `defmt`) have their own syntax. They use this to translate.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::ToTokens as _;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Expr, Ident, LitStr, Token,
+};
+
+/// A parsed message, like `"expected {expected}, got {:?}", got`.
+pub(crate) struct Message {
+    /// The message's string literal.
+    pub lit: LitStr,
+    /// The message, split into text and arguments.
+    pub pieces: Vec<Piece>,
+}
+
+/// One part of a message.
+pub(crate) enum Piece {
+    /// Some text, with any `{{` or `}}` escapes already undone.
+    Text(String),
+    /// An argument to format, like `{got:?}`.
+    Argument {
+        /// The expression to format. For `{got}`, that's the `got` binding.
+        value: TokenStream2,
+        /// Everything after the `:`, like `?` or `#x`. Empty without one.
+        spec: String,
+    },
+}
+
+/// The parts of a message before its arguments are looked up.
+struct RawMessage {
+    lit: LitStr,
+    args: Vec<Expr>,
+}
+
+impl Parse for RawMessage {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lit: LitStr = input.parse()?;
+        let args = if input.is_empty() {
+            Vec::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Punctuated::<Expr, Token![,]>::parse_terminated(input)?
+                .into_iter()
+                .collect()
+        };
+
+        Ok(Self { lit, args })
+    }
+}
+
+impl Message {
+    /// Parses the tokens of a `#[error("...")]` attribute.
+    ///
+    /// # Errors
+    ///
+    /// The message has to be a string literal with well-formed placeholders,
+    /// and every placeholder needs something to format.
+    pub(crate) fn parse(tokens: &TokenStream2) -> syn::Result<Self> {
+        let RawMessage { lit, args } = syn::parse2(tokens.clone())?;
+
+        // named arguments look like `name = value`
+        let mut positional = Vec::new();
+        let mut named = Vec::new();
+        for arg in args {
+            match arg {
+                Expr::Assign(assign) => {
+                    let maybe_name = match *assign.left {
+                        Expr::Path(ref path) => path.path.get_ident().cloned(),
+                        _ => None,
+                    };
+                    let Some(name) = maybe_name else {
+                        return Err(syn::Error::new_spanned(
+                            assign.left,
+                            "Named arguments must be given a plain name.",
+                        ));
+                    };
+                    named.push((name, assign.right.into_token_stream()));
+                }
+                other => positional.push(other.into_token_stream()),
+            }
+        }
+
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut next_positional = 0_usize;
+        let value = lit.value();
+        let mut chars = value.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(inner) => placeholder.push(inner),
+                            None => return Err(Self::err_unclosed_placeholder(&lit)),
+                        }
+                    }
+
+                    let (untrimmed_arg, spec) = placeholder
+                        .split_once(':')
+                        .unwrap_or((placeholder.as_str(), ""));
+                    let arg = untrimmed_arg.trim();
+
+                    let maybe_found = if arg.is_empty() {
+                        next_positional = next_positional.saturating_add(1);
+                        positional.get(next_positional.saturating_sub(1)).cloned()
+                    } else if let Ok(index) = arg.parse::<usize>() {
+                        positional.get(index).cloned()
+                    } else {
+                        named
+                            .iter()
+                            .find(|named_arg| named_arg.0 == arg)
+                            .map(|named_arg| named_arg.1.clone())
+                            .or_else(|| {
+                                syn::parse_str::<Ident>(arg)
+                                    .ok()
+                                    .map(|_| Ident::new(arg, lit.span()).into_token_stream())
+                            })
+                    };
+
+                    let Some(found) = maybe_found else {
+                        return Err(Self::err_missing_argument(&lit, &placeholder));
+                    };
+
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(core::mem::take(&mut text)));
+                    }
+                    pieces.push(Piece::Argument {
+                        value: found,
+                        spec: spec.to_owned(),
+                    });
+                }
+                '}' => return Err(Self::err_unopened_placeholder(&lit)),
+                other => text.push(other),
+            }
+        }
+
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+
+        Ok(Self { lit, pieces })
+    }
+
+    fn err_unclosed_placeholder(lit: &LitStr) -> syn::Error {
+        syn::Error::new_spanned(
+            lit,
+            "This message has a `{` without a matching `}`. Use `{{` to write a brace.",
+        )
+    }
+
+    fn err_unopened_placeholder(lit: &LitStr) -> syn::Error {
+        syn::Error::new_spanned(
+            lit,
+            "This message has a `}` without a matching `{`. Use `}}` to write a brace.",
+        )
+    }
+
+    fn err_missing_argument(lit: &LitStr, placeholder: &str) -> syn::Error {
+        syn::Error::new_spanned(
+            lit,
+            format!("Nothing was given to format for `{{{placeholder}}}` in this message."),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::{Message, Piece};
+
+    /// Turns the pieces back into something easy to compare.
+    fn describe(message: &Message) -> Vec<String> {
+        message
+            .pieces
+            .iter()
+            .map(|piece| match *piece {
+                Piece::Text(ref text) => format!("text {text}"),
+                Piece::Argument {
+                    ref value,
+                    ref spec,
+                } => format!("arg {value} :{spec}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn splits_text_and_arguments() {
+        let message =
+            Message::parse(&quote!("{{expected}} {expected}, got {:?} and {0:#x}", got)).unwrap();
+
+        assert_eq!(
+            describe(&message),
+            [
+                "text {expected} ",
+                "arg expected :",
+                "text , got ",
+                "arg got :?",
+                "text  and ",
+                "arg got :#x",
+            ]
+        );
+    }
+
+    #[test]
+    fn named_arguments_win_over_bindings() {
+        let message = Message::parse(&quote!("hi {name}", name = self.name())).unwrap();
+        assert_eq!(describe(&message), ["text hi ", "arg self . name () :"]);
+    }
+
+    #[test]
+    fn bad_messages_are_errors() {
+        for tokens in [
+            quote!("unclosed {"),
+            quote!("unopened }"),
+            quote!("nothing for {}"),
+            quote!("nothing for {3}", a),
+        ] {
+            assert!(Message::parse(&tokens).is_err());
+        }
+    }
+}
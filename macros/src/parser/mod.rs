@@ -1,16 +1,20 @@
+use attr::EnumAttributes;
 use proc_macro2::Span;
-use syn::{spanned::Spanned as _, DeriveInput, Generics, Ident, Item};
+use syn::{spanned::Spanned as _, DeriveInput, Generics, Ident, Item, Visibility};
 use variant::{WrappedVariant, WrappedVariantBuilder};
 
 pub(crate) mod attr;
 pub(super) mod field;
+pub(crate) mod format;
 pub(super) mod variant;
 
 pub(crate) struct UserEnum {
     ident: Ident,
+    vis: Visibility,
     generics: Generics,
     span: Span,
     after_span: Span,
+    attributes: EnumAttributes,
     variants: Vec<WrappedVariant>,
 }
 
@@ -18,13 +22,16 @@ impl UserEnum {
     /// Attempts to parse the user's given enum into its required components.
     pub(crate) fn new(input: DeriveInput) -> syn::Result<Self> {
         // check if we've been given an enum
-        let (span, generics, after_span, ident, variants) = match Item::from(input) {
+        let (span, generics, after_span, attributes, ident, vis, variants) = match Item::from(input)
+        {
             #[rustfmt::skip]
             Item::Enum(item) => {(
                     item.span(),
                     item.generics,
                     item.brace_token.span.close(),
+                    EnumAttributes::parse(item.enum_token.span, &item.attrs)?,
                     item.ident,
+                    item.vis,
                     item.variants // check each variant
                         .into_iter()
                         .map(|v| WrappedVariantBuilder::new(v).build())
@@ -37,9 +44,11 @@ impl UserEnum {
 
         Ok(Self {
             ident,
+            vis,
             generics,
             span,
             after_span,
+            attributes,
             variants,
         })
     }
@@ -49,6 +58,11 @@ impl UserEnum {
         self.ident.clone()
     }
 
+    /// The enum's visibility, like `pub`.
+    pub(crate) const fn vis(&self) -> &Visibility {
+        &self.vis
+    }
+
     /// Generic bounds (including lifetimes) on the enum.
     pub(crate) const fn generics(&self) -> &Generics {
         &self.generics
@@ -64,6 +78,11 @@ impl UserEnum {
         self.after_span
     }
 
+    /// Options given to the enum's own `#[error(...)]` attribute.
+    pub(crate) const fn attributes(&self) -> &EnumAttributes {
+        &self.attributes
+    }
+
     /// The available variants on the given enum.
     pub(crate) const fn variants(&self) -> &Vec<WrappedVariant> {
         &self.variants
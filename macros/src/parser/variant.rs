@@ -1,13 +1,13 @@
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
 use syn::{
-    punctuated::Punctuated, spanned::Spanned as _, Attribute, Ident, Meta, Path, PathSegment,
-    Variant,
+    punctuated::Punctuated, spanned::Spanned as _, Attribute, Expr, ExprLit, Ident, Lit, Meta,
+    Path, PathSegment, Variant,
 };
 
 use super::{
-    attr::{ErrorAttribute, FromAttribute},
-    field::{self, FieldsType, WrappedField, WrappedFieldBuilder, WrappedFields},
+    attr::{ErrorAttribute, FromAttribute, VariantOptions},
+    field::{self, FieldsType, WrappedField, WrappedFieldBuilder, WrappedFieldInfo, WrappedFields},
 };
 
 /// A method to build a `WrappedVariant`.
@@ -67,6 +67,11 @@ impl FromAttributeCheck {
             .map(move |f| WrappedFieldBuilder::new(f).build())
             .collect::<syn::Result<Vec<_>>>()?;
 
+        // only one field can be the source
+        if let Some(second_source) = fields.iter().filter(|f| f.info().source).nth(1) {
+            return Err(Self::err_multiple_source_fields(second_source.info().span));
+        }
+
         let has_from_field = fields.iter().any(WrappedField::has_from_attribute);
         let mut from = None;
 
@@ -98,6 +103,14 @@ impl FromAttributeCheck {
         })
     }
 
+    /// An error for variants with more than one `#[source]` field.
+    fn err_multiple_source_fields(field_span: Span) -> syn::Error {
+        syn::Error::new(
+            field_span,
+            "A variant may only have one field with the `#[source]` attribute.",
+        )
+    }
+
     /// An error asking users to remove additional fields when using
     /// the from attribute.
     pub(crate) fn err_nonfrom_fields_not_permitted(field_span: Span) -> syn::Error {
@@ -116,12 +129,15 @@ impl FromAttributeCheck {
 /// - the error tag should either:
 ///    - have a string, or be
 ///    - #[error(transparent)] for a from variant
+/// - any other error tags hold options, like `#[error(status = 404)]`.
 pub(crate) struct ErrorAttributeCheck {
     /// not all variants use a `#[from]` attr
     ident: Ident,
     fields: WrappedFields,
     from_attribute: Option<FromAttribute>,
     error_attribute: ErrorAttribute,
+    options: VariantOptions,
+    doc: String,
 }
 
 impl ErrorAttributeCheck {
@@ -136,7 +152,18 @@ impl ErrorAttributeCheck {
 
         let error_attribute_path = crate::util::create_path(span, &["error"]);
 
-        let mut error_attributes = attrs.iter().filter(|a| a.path() == &error_attribute_path);
+        // options go in their own attributes, so grab those first
+        let mut options = VariantOptions::default();
+        for attr in attrs.iter().filter(|a| a.path() == &error_attribute_path) {
+            if VariantOptions::is_options_attribute(attr) {
+                options.parse_attribute(attr)?;
+            }
+        }
+
+        let mut error_attributes = attrs
+            .iter()
+            .filter(|a| a.path() == &error_attribute_path)
+            .filter(|a| !VariantOptions::is_options_attribute(a));
 
         // warning: this mutates error_attributes (the iterator is being consumed)
         let (first, second) = (error_attributes.next(), error_attributes.next());
@@ -164,13 +191,14 @@ impl ErrorAttributeCheck {
                     return Err(Self::err_nothing_to_display(attr));
                 }
 
-                let transparent_check =
-                    { attr_args.tokens.to_string() == ErrorAttribute::TRANSPARENT_LITERAL };
+                let attr_args_str = attr_args.tokens.to_string();
+                let transparent_check = { attr_args_str == ErrorAttribute::TRANSPARENT_LITERAL };
+                let many_check = { attr_args_str == ErrorAttribute::MANY_LITERAL };
 
                 // let transparent_attr_path =
                 //     &util::create_path(span, &[ErrorAttribute::TRANSPARENT_LITERAL]);
 
-                // check if we're stringy or just have `transparent`
+                // check if we're stringy or just have `transparent`/`many`
                 if transparent_check {
                     // ok now make sure we have a `#[from]` attr
                     if from_attribute.is_none() {
@@ -178,6 +206,18 @@ impl ErrorAttributeCheck {
                     }
 
                     ErrorAttribute::Transparent
+                } else if many_check {
+                    // we'll display each item in the one field
+                    let field_count = match fields {
+                        WrappedFields::Named(ref f) | WrappedFields::Unnamed(ref f) => f.len(),
+                        WrappedFields::Unit => 0,
+                    };
+
+                    if field_count != 1 {
+                        return Err(Self::err_many_requires_one_field(attr));
+                    }
+
+                    ErrorAttribute::Many
                 } else {
                     ErrorAttribute::Stringy(attr_args.tokens.clone())
                 }
@@ -189,6 +229,8 @@ impl ErrorAttributeCheck {
             fields,
             from_attribute,
             error_attribute,
+            options,
+            doc: Self::doc_comment(&attrs),
         })
     }
 
@@ -199,9 +241,42 @@ impl ErrorAttributeCheck {
             fields: self.fields,
             from_attribute: self.from_attribute,
             error_attribute: self.error_attribute,
+            options: self.options,
+            doc: self.doc,
         }
     }
 
+    /// Joins the lines of the variant's doc comment, if it has one.
+    fn doc_comment(attrs: &[Attribute]) -> String {
+        let lines = attrs.iter().filter_map(|attr| {
+            let Meta::NameValue(ref name_value) = attr.meta else {
+                return None;
+            };
+            if !name_value.path.is_ident("doc") {
+                return None;
+            }
+
+            match name_value.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(ref lit),
+                    ..
+                }) => Some(lit.value()),
+                _ => None,
+            }
+        });
+
+        // `/// like this` becomes `" like this"`, so drop that first space
+        lines
+            .map(|line| {
+                line.strip_prefix(' ')
+                    .map_or_else(|| line.clone(), str::to_owned)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_owned()
+    }
+
     fn err_missing_error_attr(span: Span) -> syn::Error {
         syn::Error::new(
             span,
@@ -212,7 +287,7 @@ impl ErrorAttributeCheck {
     fn err_multiple_error_attrs(second_attr: &Attribute) -> syn::Error {
         syn::Error::new_spanned(
             second_attr,
-            "Each variant may only have one `#[error(...)]` attribute.",
+            "Each variant may only have one `#[error(...)]` attribute with a message.",
         )
     }
 
@@ -232,6 +307,14 @@ impl ErrorAttributeCheck {
         )
     }
 
+    fn err_many_requires_one_field(attr: &Attribute) -> syn::Error {
+        syn::Error::new_spanned(
+            attr,
+            "An `#[error(many)]` attribute requires a variant with exactly one \
+            field, holding a collection of errors (like a `Vec<E>`).",
+        )
+    }
+
     fn err_transparent_requires_from_variant(attr: &Attribute) -> syn::Error {
         syn::Error::new_spanned(
             attr,
@@ -247,6 +330,9 @@ pub(crate) struct WrappedVariant {
     pub fields: WrappedFields,
     pub from_attribute: Option<FromAttribute>,
     pub error_attribute: ErrorAttribute,
+    pub options: VariantOptions,
+    /// The variant's doc comment, without the `///`s.
+    pub doc: String,
 }
 
 impl WrappedVariant {
@@ -276,10 +362,111 @@ impl WrappedVariant {
         }
     }
 
+    /// A match head that only binds the `#[from]` field, if there is one.
+    /// The field is bound by reference, unless `by_ref` is false.
+    ///
+    /// Returns the head alongside the identifier it binds. For example:
+    /// `SomeEnum::SomeVariant(ref e)` and `e`.
+    pub(crate) fn match_head_with_from(
+        &self,
+        enum_ident: Ident,
+        by_ref: bool,
+    ) -> Option<(TokenStream2, Ident)> {
+        let info = self.from_attribute.as_ref()?;
+        let variant_path = self.variant_path(enum_ident);
+        let binding_mode = by_ref.then(|| quote!(ref));
+
+        match self.fields {
+            WrappedFields::Named(_) => {
+                let ident = info.ident.clone()?;
+                Some((quote! { #variant_path { #binding_mode #ident } }, ident))
+            }
+            WrappedFields::Unnamed(_) => {
+                let ident = Ident::new("e", info.span);
+                Some((quote! { #variant_path(#binding_mode #ident) }, ident))
+            }
+            WrappedFields::Unit => None,
+        }
+    }
+
+    /// The binding `filled_match_head` gives this variant's first field. For
+    /// `transparent` and `many` variants, that's their only field.
+    pub(crate) fn first_field_binding(&self) -> TokenStream2 {
+        match self.fields {
+            WrappedFields::Named(ref fields) => {
+                let ident = fields.first().and_then(|field| field.info().ident.clone());
+                quote!(#ident)
+            }
+            WrappedFields::Unnamed(_) | WrappedFields::Unit => quote!(_0),
+        }
+    }
+
+    /// A match head that only binds the fields `included` picks, by
+    /// reference. For example: `SomeEnum::SomeVariant(ref _0, _, ref _2)`.
+    ///
+    /// Returns the head alongside each bound identifier and its field's name.
+    /// Unnamed fields are named by their position, like `"0"`.
+    pub(crate) fn partial_match_head<F>(
+        &self,
+        enum_ident: Ident,
+        included: F,
+    ) -> (TokenStream2, Vec<(Ident, String)>)
+    where
+        F: Fn(&WrappedField) -> bool,
+    {
+        let variant_path = self.variant_path(enum_ident);
+
+        let fields = match self.fields {
+            WrappedFields::Named(ref fields) | WrappedFields::Unnamed(ref fields) => fields,
+            WrappedFields::Unit => return (quote!(#variant_path), Vec::new()),
+        };
+
+        let bound = fields
+            .iter()
+            .enumerate()
+            .filter(|&(_, f)| included(f))
+            .map(|(i, field)| match field.info().ident {
+                Some(ref ident) => (ident.clone(), ident.to_string()),
+                None => (quote::format_ident!("_{}", i), i.to_string()),
+            })
+            .collect::<Vec<_>>();
+
+        let match_head = if let WrappedFields::Named(_) = self.fields {
+            let bindings = bound.iter().map(|binding| &binding.0);
+            quote! { #variant_path { #(ref #bindings,)* .. } }
+        } else {
+            let bindings = fields.iter().enumerate().map(|(i, f)| {
+                if included(f) {
+                    let ident = quote::format_ident!("_{}", i);
+                    quote!(ref #ident)
+                } else {
+                    quote!(_)
+                }
+            });
+            quote! { #variant_path(#(#bindings),*) }
+        };
+
+        (match_head, bound)
+    }
+
+    /// The field marked with `#[source]`, if there is one.
+    pub(crate) fn source_field(&self) -> Option<&WrappedFieldInfo> {
+        match self.fields {
+            WrappedFields::Named(ref fields) | WrappedFields::Unnamed(ref fields) => fields
+                .iter()
+                .map(WrappedField::info)
+                .find(|info| info.source),
+            WrappedFields::Unit => None,
+        }
+    }
+
     /// A match head that's filled with identifiers. For example:
-    /// `SomeEnum::SomeVariant::(_0, _1, _2)`
-    pub(crate) fn filled_match_head(&self, enum_ident: Ident) -> TokenStream2 {
+    /// `SomeEnum::SomeVariant::(ref _0, ref _1, ref _2)`
+    ///
+    /// The fields are bound by reference, unless `by_ref` is false.
+    pub(crate) fn filled_match_head(&self, enum_ident: Ident, by_ref: bool) -> TokenStream2 {
         let variant_path = self.variant_path(enum_ident);
+        let binding_mode = by_ref.then(|| quote!(ref));
 
         match self.fields {
             WrappedFields::Named(ref n) => {
@@ -289,7 +476,7 @@ impl WrappedVariant {
                             &info.ident
                         }
                     };
-                    quote!(ref #name)
+                    quote!(#binding_mode #name)
                 });
 
                 quote! {
@@ -300,7 +487,7 @@ impl WrappedVariant {
             WrappedFields::Unnamed(ref un) => {
                 let field_range = (0..un.len()).map(|i| {
                     let ident = quote::format_ident!("_{}", i);
-                    quote!(ref #ident)
+                    quote!(#binding_mode #ident)
                 });
 
                 // FIXME(#14): users currently have to do `_0` which is... bad
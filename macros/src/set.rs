@@ -0,0 +1,353 @@
+//! # Set
+//!
+//! Contains the `error_set!` part of pisserror. Each set becomes an enum
+//! using `#[derive(Error)]`, and sets made from other sets get their
+//! variants copied in, along with conversions between the two.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens as _};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token, Attribute, Fields, Ident, Token, Variant, Visibility,
+};
+
+/// Everything given to `error_set!`.
+pub(crate) struct ErrorSets {
+    sets: Vec<ErrorSet>,
+}
+
+/// One set, like `pub LoadError = ParseError || { Io(#[from] io::Error) };`.
+struct ErrorSet {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    parts: Vec<SetPart>,
+}
+
+/// One side of a `||`.
+enum SetPart {
+    /// Another set, whose variants are all copied in.
+    Set(Ident),
+    /// Some new variants, written like the inside of an enum.
+    Variants(Punctuated<Variant, Token![,]>),
+}
+
+/// A set whose variants are known, for the sets after it to use.
+struct ResolvedSet<'set> {
+    ident: &'set Ident,
+    variants: Vec<&'set Variant>,
+}
+
+impl Parse for ErrorSets {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut sets = Vec::new();
+
+        while !input.is_empty() {
+            let attrs = input.call(Attribute::parse_outer)?;
+            let vis = input.parse()?;
+            let ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            let mut parts = Vec::new();
+            loop {
+                if input.peek(token::Brace) {
+                    let content;
+                    braced!(content in input);
+                    parts.push(SetPart::Variants(Punctuated::parse_terminated(&content)?));
+                } else {
+                    parts.push(SetPart::Set(input.parse()?));
+                }
+
+                if input.parse::<Option<Token![||]>>()?.is_none() {
+                    break;
+                }
+            }
+
+            sets.push(ErrorSet {
+                attrs,
+                vis,
+                ident,
+                parts,
+            });
+
+            // each set ends with a semicolon, though the last one may not
+            if !input.is_empty() {
+                input.parse::<Token![;]>()?;
+            }
+        }
+
+        Ok(Self { sets })
+    }
+}
+
+impl ErrorSets {
+    /// Makes each set's enum, plus conversions from the sets it was made
+    /// from.
+    ///
+    /// # Errors
+    ///
+    /// Sets can only use sets defined before them, and two variants with the
+    /// same name must be the same variant.
+    pub(crate) fn expand(&self) -> syn::Result<TokenStream2> {
+        let mut resolved: Vec<ResolvedSet<'_>> = Vec::new();
+        let mut output = TokenStream2::new();
+
+        for set in &self.sets {
+            let mut variants: Vec<&Variant> = Vec::new();
+            let mut subsets = Vec::new();
+
+            for part in &set.parts {
+                match *part {
+                    SetPart::Set(ref ident) => {
+                        let Some(subset) = resolved.iter().find(|r| r.ident == ident) else {
+                            return Err(Self::err_unknown_set(ident));
+                        };
+
+                        for variant in &subset.variants {
+                            Self::add_variant(&mut variants, variant, &set.ident)?;
+                        }
+                        subsets.push(subset);
+                    }
+                    SetPart::Variants(ref list) => {
+                        for variant in list {
+                            Self::add_variant(&mut variants, variant, &set.ident)?;
+                        }
+                    }
+                }
+            }
+
+            let ErrorSet {
+                ref attrs,
+                ref vis,
+                ref ident,
+                ..
+            } = *set;
+
+            output.extend(quote! {
+                #[derive(::pisserror::Error)]
+                #(#attrs)*
+                #vis enum #ident {
+                    #(#variants),*
+                }
+            });
+
+            for subset in subsets {
+                output.extend(Self::conversions(subset, ident, variants.len()));
+            }
+
+            resolved.push(ResolvedSet { ident, variants });
+        }
+
+        Ok(output)
+    }
+
+    /// Adds a variant to a set, unless it's already there.
+    fn add_variant<'set>(
+        variants: &mut Vec<&'set Variant>,
+        variant: &'set Variant,
+        set_ident: &Ident,
+    ) -> syn::Result<()> {
+        match variants.iter().find(|v| v.ident == variant.ident) {
+            // the same variant can come from more than one set
+            Some(existing) => {
+                if existing.to_token_stream().to_string() != variant.to_token_stream().to_string() {
+                    return Err(Self::err_conflicting_variant(variant, set_ident));
+                }
+            }
+            None => variants.push(variant),
+        }
+
+        Ok(())
+    }
+
+    /// A `From` implementation that moves each of the subset's variants into
+    /// the same variant of the superset, and a `TryFrom` that goes back.
+    fn conversions(
+        subset: &ResolvedSet<'_>,
+        superset: &Ident,
+        superset_len: usize,
+    ) -> TokenStream2 {
+        let subset_ident = subset.ident;
+
+        let (from_arms, try_from_arms): (Vec<_>, Vec<_>) = subset
+            .variants
+            .iter()
+            .map(|v| {
+                let variant_ident = &v.ident;
+                let fields = Self::field_pattern(&v.fields);
+                (
+                    quote! { #subset_ident::#variant_ident #fields => #superset::#variant_ident #fields },
+                    quote! {
+                        #superset::#variant_ident #fields => ::core::result::Result::Ok(#subset_ident::#variant_ident #fields)
+                    },
+                )
+            })
+            .unzip();
+
+        // superset variants that aren't in the subset can't be converted
+        let other_arm = (superset_len > subset.variants.len())
+            .then(|| quote! { other => ::core::result::Result::Err(other), });
+
+        quote! {
+            #[automatically_derived]
+            impl ::core::convert::From<#subset_ident> for #superset {
+                fn from(error: #subset_ident) -> Self {
+                    match error {
+                        #(#from_arms,)*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl ::core::convert::TryFrom<#superset> for #subset_ident {
+                type Error = #superset;
+
+                /// Gets the subset's error back, or returns the error if its
+                /// variant isn't in the subset.
+                fn try_from(error: #superset) -> ::core::result::Result<Self, Self::Error> {
+                    match error {
+                        #(#try_from_arms,)*
+                        #other_arm
+                    }
+                }
+            }
+        }
+    }
+
+    /// Binds every field, like `{ path, source }` or `(_0, _1)`. The same
+    /// tokens build the variant back up.
+    fn field_pattern(fields: &Fields) -> TokenStream2 {
+        match *fields {
+            Fields::Named(ref named) => {
+                let idents = named.named.iter().map(|f| &f.ident);
+                quote! { { #(#idents),* } }
+            }
+            Fields::Unnamed(ref unnamed) => {
+                let idents = (0..unnamed.unnamed.len()).map(|i| format_ident!("_{}", i));
+                quote! { ( #(#idents),* ) }
+            }
+            Fields::Unit => TokenStream2::new(),
+        }
+    }
+
+    fn err_unknown_set(ident: &Ident) -> syn::Error {
+        syn::Error::new_spanned(
+            ident,
+            format!(
+                "There's no set named `{ident}` before this one. Sets can only be made from \
+                sets defined earlier in the same `error_set!`."
+            ),
+        )
+    }
+
+    fn err_conflicting_variant(variant: &Variant, set_ident: &Ident) -> syn::Error {
+        syn::Error::new_spanned(
+            &variant.ident,
+            format!(
+                "`{set_ident}` already has a different variant named `{}`. Variants with the \
+                same name must be the same.",
+                variant.ident
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::parse_quote;
+
+    use super::ErrorSets;
+
+    #[test]
+    fn supersets_flatten_their_subsets() {
+        let sets: ErrorSets = parse_quote! {
+            /// Parsing failed.
+            #[derive(Debug)]
+            pub ParseError = {
+                #[error("unexpected end of input")]
+                Eof,
+                #[error("bad token `{_0}`")]
+                BadToken(char),
+            };
+            LoadError = ParseError || {
+                #[error("couldn't read `{path}`")]
+                Read { path: String },
+            }
+        };
+
+        let expected: TokenStream2 = parse_quote! {
+            #[derive(::pisserror::Error)]
+            /// Parsing failed.
+            #[derive(Debug)]
+            pub enum ParseError {
+                #[error("unexpected end of input")]
+                Eof,
+                #[error("bad token `{_0}`")]
+                BadToken(char)
+            }
+
+            #[derive(::pisserror::Error)]
+            enum LoadError {
+                #[error("unexpected end of input")]
+                Eof,
+                #[error("bad token `{_0}`")]
+                BadToken(char),
+                #[error("couldn't read `{path}`")]
+                Read { path: String }
+            }
+
+            #[automatically_derived]
+            impl ::core::convert::From<ParseError> for LoadError {
+                fn from(error: ParseError) -> Self {
+                    match error {
+                        ParseError::Eof => LoadError::Eof,
+                        ParseError::BadToken(_0) => LoadError::BadToken(_0),
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl ::core::convert::TryFrom<LoadError> for ParseError {
+                type Error = LoadError;
+
+                /// Gets the subset's error back, or returns the error if its
+                /// variant isn't in the subset.
+                fn try_from(error: LoadError) -> ::core::result::Result<Self, Self::Error> {
+                    match error {
+                        LoadError::Eof => ::core::result::Result::Ok(ParseError::Eof),
+                        LoadError::BadToken(_0) => ::core::result::Result::Ok(ParseError::BadToken(_0)),
+                        other => ::core::result::Result::Err(other),
+                    }
+                }
+            }
+        };
+
+        assert_eq!(expected.to_string(), sets.expand().unwrap().to_string());
+    }
+
+    #[test]
+    fn sets_must_exist_and_agree() {
+        let unknown: ErrorSets = parse_quote! {
+            LoadError = ParseError || {
+                #[error("oops")]
+                Oops,
+            };
+        };
+        assert!(unknown.expand().is_err());
+
+        let conflicting: ErrorSets = parse_quote! {
+            A = {
+                #[error("oops")]
+                Oops,
+            };
+            B = A || {
+                #[error("a different oops")]
+                Oops,
+            };
+        };
+        assert!(conflicting.expand().is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! # Accessors
+//!
+//! Makes `is_*`, `as_*`, and `into_*` methods for each of the user's
+//! variants, if they asked for them with `#[error(accessors)]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+use crate::{
+    parser::{field::WrappedFields, variant::WrappedVariant, UserEnum},
+    util,
+};
+
+impl UserEnum {
+    /// An `is_*` method for each variant, and `as_*` and `into_*` methods
+    /// for each variant with fields.
+    ///
+    /// Variants with one field give it back alone. Variants with more give
+    /// back a tuple of all of them, in order.
+    pub(crate) fn accessors(&self) -> Option<TokenStream2> {
+        if !self.attributes().accessors {
+            return None;
+        }
+
+        // with only one variant, there's nothing else to match on
+        let has_others = self.variants().len() > 1;
+
+        let accessors = self.variants().iter().map(|v| {
+            let vis = self.vis();
+            let snake = util::snake_case(&v.ident);
+            let is_ident = format_ident!("is_{}", snake);
+            let match_head = v.match_head(self.ident());
+
+            let is_doc = format!("Whether this is a `{}::{}`.", self.ident(), v.ident);
+            let is_method = quote! {
+                #[doc = #is_doc]
+                #[must_use]
+                #vis const fn #is_ident(&self) -> bool {
+                    matches!(*self, #match_head)
+                }
+            };
+
+            let field_methods = self.field_accessors(v, &snake, has_others);
+            quote! {
+                #is_method
+                #field_methods
+            }
+        });
+
+        Some(quote! { #(#accessors)* })
+    }
+
+    /// The `as_*` and `into_*` methods for one variant, if it has any fields.
+    fn field_accessors(&self, v: &WrappedVariant, snake: &Ident, has_others: bool) -> TokenStream2 {
+        let fields = match v.fields {
+            WrappedFields::Named(ref fields) | WrappedFields::Unnamed(ref fields) => fields,
+            WrappedFields::Unit => return TokenStream2::new(),
+        };
+
+        let vis = self.vis();
+        let as_ident = format_ident!("as_{}", snake);
+        let into_ident = format_ident!("into_{}", snake);
+        let ref_head = v.filled_match_head(self.ident(), true);
+        let owned_head = v.filled_match_head(self.ident(), false);
+
+        let (bindings, types): (Vec<_>, Vec<_>) = fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let binding = f
+                    .info()
+                    .ident
+                    .clone()
+                    .unwrap_or_else(|| format_ident!("_{}", i));
+                (binding, &f.info().ty)
+            })
+            .unzip();
+        let single = (fields.len() == 1)
+            .then(|| bindings.first().zip(types.first()))
+            .flatten();
+        let (value, ref_ty, owned_ty) = if let Some((binding, ty)) = single {
+            (quote!(#binding), quote!(&#ty), quote!(#ty))
+        } else {
+            (
+                quote!((#(#bindings),*)),
+                quote!((#(&#types),*)),
+                quote!((#(#types),*)),
+            )
+        };
+
+        let none_arm = has_others.then(|| quote! { _ => ::core::option::Option::None, });
+        let err_arm = has_others.then(|| quote! { other => ::core::result::Result::Err(other), });
+
+        let path = format!("`{}::{}`", self.ident(), v.ident);
+        let as_doc = format!("The {path}'s fields, if this is one.");
+        let into_doc =
+            format!("Takes the fields out of a {path}, or gives the error back if it's not one.");
+
+        quote! {
+            #[doc = #as_doc]
+            #[must_use]
+            #vis fn #as_ident(&self) -> ::core::option::Option<#ref_ty> {
+                match *self {
+                    #ref_head => ::core::option::Option::Some(#value),
+                    #none_arm
+                }
+            }
+
+            #[doc = #into_doc]
+            #vis fn #into_ident(self) -> ::core::result::Result<#owned_ty, Self> {
+                match self {
+                    #owned_head => ::core::result::Result::Ok(#value),
+                    #err_arm
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn accessors_bind_every_field() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(accessors)]
+            pub enum Piss {
+                #[error("no value for `{key}`")]
+                KeyNotFound { key: String },
+                #[error("bad token `{_0}` at {_1}")]
+                BadToken(char, usize),
+                #[error("unexpected end of input")]
+                Eof,
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            #[doc = "Whether this is a `Piss::KeyNotFound`."]
+            #[must_use]
+            pub const fn is_key_not_found(&self) -> bool {
+                matches!(*self, Piss::KeyNotFound{..})
+            }
+
+            #[doc = "The `Piss::KeyNotFound`'s fields, if this is one."]
+            #[must_use]
+            pub fn as_key_not_found(&self) -> ::core::option::Option<&String> {
+                match *self {
+                    Piss::KeyNotFound{ref key} => ::core::option::Option::Some(key),
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #[doc = "Takes the fields out of a `Piss::KeyNotFound`, or gives the error back if it's not one."]
+            pub fn into_key_not_found(self) -> ::core::result::Result<String, Self> {
+                match self {
+                    Piss::KeyNotFound{key} => ::core::result::Result::Ok(key),
+                    other => ::core::result::Result::Err(other),
+                }
+            }
+
+            #[doc = "Whether this is a `Piss::BadToken`."]
+            #[must_use]
+            pub const fn is_bad_token(&self) -> bool {
+                matches!(*self, Piss::BadToken(..))
+            }
+
+            #[doc = "The `Piss::BadToken`'s fields, if this is one."]
+            #[must_use]
+            pub fn as_bad_token(&self) -> ::core::option::Option<(&char, &usize)> {
+                match *self {
+                    Piss::BadToken(ref _0, ref _1) => ::core::option::Option::Some((_0, _1)),
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #[doc = "Takes the fields out of a `Piss::BadToken`, or gives the error back if it's not one."]
+            pub fn into_bad_token(self) -> ::core::result::Result<(char, usize), Self> {
+                match self {
+                    Piss::BadToken(_0, _1) => ::core::result::Result::Ok((_0, _1)),
+                    other => ::core::result::Result::Err(other),
+                }
+            }
+
+            #[doc = "Whether this is a `Piss::Eof`."]
+            #[must_use]
+            pub const fn is_eof(&self) -> bool {
+                matches!(*self, Piss::Eof)
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.accessors().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! # Catalog
+//!
+//! Makes the `ERROR_CATALOG` list for the user's error type, describing each
+//! variant for documentation, if they asked for it with `#[error(catalog)]`.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{LitInt, LitStr};
+
+use crate::{
+    parser::{attr::ErrorAttribute, field::WrappedFields, variant::WrappedVariant, UserEnum},
+    util,
+};
+
+impl UserEnum {
+    /// An `ERROR_CATALOG` of `pisserror::ErrorInfo`s, one for each variant.
+    ///
+    /// This is only made if the user asked for it with `#[error(catalog)]`.
+    pub(crate) fn error_catalog(&self) -> Option<TokenStream2> {
+        if !self.attributes().catalog {
+            return None;
+        }
+
+        let infos = self.variants().iter().map(|v| {
+            let name = v.ident.to_string();
+            let code = Self::optional(v.options.errno.map(|code| {
+                let lit = LitInt::new(&code.value.unsigned_abs().to_string(), Span::call_site());
+                if code.value < 0 {
+                    quote!(-#lit)
+                } else {
+                    quote!(#lit)
+                }
+            }));
+            let message = Self::optional(Self::message_template(v).map(|lit| quote!(#lit)));
+            let fields = Self::field_infos(v);
+            let doc = &v.doc;
+            let status = Self::optional(
+                v.options
+                    .status
+                    .or_else(|| self.attributes().status)
+                    .map(|s| quote!(#s)),
+            );
+            let exit_code = Self::optional(
+                v.options
+                    .exit_code
+                    .or_else(|| self.attributes().exit_code)
+                    .map(|e| quote!(#e)),
+            );
+
+            quote! {
+                ::pisserror::ErrorInfo::new(
+                    #name,
+                    #code,
+                    #message,
+                    &[#(#fields),*],
+                    #doc,
+                    #status,
+                    #exit_code,
+                )
+            }
+        });
+
+        Some(quote! {
+            /// Describes each variant: its name, code, message, fields, docs,
+            /// and status and exit codes.
+            pub const ERROR_CATALOG: &'static [::pisserror::ErrorInfo] = &[#(#infos),*];
+        })
+    }
+
+    /// The variant's `#[error(...)]` string, without any fields filled in.
+    fn message_template(v: &WrappedVariant) -> Option<LitStr> {
+        match v.error_attribute {
+            ErrorAttribute::Stringy(ref tokens) => tokens
+                .clone()
+                .into_iter()
+                .next()
+                .and_then(|first| syn::parse2::<LitStr>(first.into()).ok()),
+            ErrorAttribute::Transparent | ErrorAttribute::Many => None,
+        }
+    }
+
+    /// A `pisserror::FieldInfo` for each field, in order.
+    fn field_infos(v: &WrappedVariant) -> Vec<TokenStream2> {
+        let fields = match v.fields {
+            WrappedFields::Named(ref fields) | WrappedFields::Unnamed(ref fields) => fields,
+            WrappedFields::Unit => return Vec::new(),
+        };
+
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let name = field
+                    .info()
+                    .ident
+                    .as_ref()
+                    .map_or_else(|| i.to_string(), ToString::to_string);
+                let ty = util::type_name(&field.info().ty);
+                quote! { ::pisserror::FieldInfo::new(#name, #ty) }
+            })
+            .collect()
+    }
+
+    /// Wraps the value in `Some`, or makes a `None`.
+    fn optional(value: Option<TokenStream2>) -> TokenStream2 {
+        value.map_or_else(
+            || quote!(::core::option::Option::None),
+            |inner| quote!(::core::option::Option::Some(#inner)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn catalog_describes_each_variant() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(catalog, status = 500)]
+            enum Piss {
+                /// The key isn't in the table.
+                ///
+                /// Check your spelling.
+                #[error("no value for `{key}` in {table:?}")]
+                #[error(status = 404, exit_code = 2)]
+                KeyNotFound { key: String, table: Vec<&'static str> },
+                #[error(transparent)]
+                Io(#[from] std::io::Error),
+            }
+        };
+        if !cfg!(feature = "catalog") {
+            assert!(UserEnum::new(sauce.into()).is_err());
+            return;
+        }
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            /// Describes each variant: its name, code, message, fields, docs,
+            /// and status and exit codes.
+            pub const ERROR_CATALOG: &'static [::pisserror::ErrorInfo] = &[
+                ::pisserror::ErrorInfo::new(
+                    "KeyNotFound",
+                    ::core::option::Option::None,
+                    ::core::option::Option::Some("no value for `{key}` in {table:?}"),
+                    &[
+                        ::pisserror::FieldInfo::new("key", "String"),
+                        ::pisserror::FieldInfo::new("table", "Vec<&'static str>")
+                    ],
+                    "The key isn't in the table.\n\nCheck your spelling.",
+                    ::core::option::Option::Some(404u16),
+                    ::core::option::Option::Some(2u8),
+                ),
+                ::pisserror::ErrorInfo::new(
+                    "Io",
+                    ::core::option::Option::None,
+                    ::core::option::Option::None,
+                    &[::pisserror::FieldInfo::new("0", "std::io::Error")],
+                    "",
+                    ::core::option::Option::Some(500u16),
+                    ::core::option::Option::None,
+                )
+            ];
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.error_catalog().unwrap().to_string()
+        );
+    }
+}
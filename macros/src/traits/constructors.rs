@@ -0,0 +1,149 @@
+//! # Constructors
+//!
+//! Makes a function to create each of the user's variants, if they asked for
+//! them with `#[error(constructors)]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+
+use crate::{
+    parser::{field::WrappedFields, variant::WrappedVariant, UserEnum},
+    util,
+};
+
+impl UserEnum {
+    /// A `#[must_use]` function for each variant, named after it in
+    /// snake_case, like `MyError::malformed_header(expected, got)`.
+    ///
+    /// Each field becomes an argument taking anything that goes `Into` it.
+    pub(crate) fn constructors(&self) -> Option<TokenStream2> {
+        if !self.attributes().constructors {
+            return None;
+        }
+
+        let constructors = self.variants().iter().map(|v| self.constructor(v));
+        Some(quote! { #(#constructors)* })
+    }
+
+    /// The constructor for one variant.
+    fn constructor(&self, v: &WrappedVariant) -> TokenStream2 {
+        let vis = self.vis();
+        let fn_ident = util::snake_case(&v.ident);
+        let variant_path = v.variant_path(self.ident());
+
+        let fields = match v.fields {
+            WrappedFields::Named(ref fields) | WrappedFields::Unnamed(ref fields) => {
+                fields.as_slice()
+            }
+            WrappedFields::Unit => &[],
+        };
+
+        // `#[from]` variants take their source, just like `From::from`
+        let (args, values): (Vec<_>, Vec<_>) = fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let arg = match f.info().ident {
+                    Some(ref ident) => ident.clone(),
+                    None if f.has_from_attribute() => format_ident!("source"),
+                    None => format_ident!("_{}", i),
+                };
+                let ty = &f.info().ty;
+                (
+                    quote! { #arg: impl ::core::convert::Into<#ty> },
+                    quote! { ::core::convert::Into::into(#arg) },
+                )
+            })
+            .unzip();
+
+        let construct = match v.fields {
+            WrappedFields::Named(_) => {
+                let idents = fields.iter().map(|f| &f.info().ident);
+                quote! { #variant_path { #(#idents: #values),* } }
+            }
+            WrappedFields::Unnamed(_) => quote! { #variant_path(#(#values),*) },
+            WrappedFields::Unit => quote! { #variant_path },
+        };
+
+        let doc = if v.from_attribute.is_some() {
+            format!(
+                "Creates a `{}::{}` from its source, like `From::from`.",
+                self.ident(),
+                v.ident
+            )
+        } else {
+            format!("Creates a `{}::{}`.", self.ident(), v.ident)
+        };
+
+        quote! {
+            #[doc = #doc]
+            #[must_use]
+            #vis fn #fn_ident(#(#args),*) -> Self {
+                #construct
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn constructors_take_each_field() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(constructors)]
+            pub enum Piss {
+                #[error("expected `{expected}`, got `{got}`")]
+                MalformedHeader { expected: String, got: String },
+                #[error("the request took {_0} seconds")]
+                HTTPTimeout(u64),
+                #[error(transparent)]
+                DiskError(#[from] std::io::Error),
+                #[error("no type")]
+                Type,
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            #[doc = "Creates a `Piss::MalformedHeader`."]
+            #[must_use]
+            pub fn malformed_header(
+                expected: impl ::core::convert::Into<String>,
+                got: impl ::core::convert::Into<String>
+            ) -> Self {
+                Piss::MalformedHeader {
+                    expected: ::core::convert::Into::into(expected),
+                    got: ::core::convert::Into::into(got)
+                }
+            }
+
+            #[doc = "Creates a `Piss::HTTPTimeout`."]
+            #[must_use]
+            pub fn http_timeout(_0: impl ::core::convert::Into<u64>) -> Self {
+                Piss::HTTPTimeout(::core::convert::Into::into(_0))
+            }
+
+            #[doc = "Creates a `Piss::DiskError` from its source, like `From::from`."]
+            #[must_use]
+            pub fn disk_error(source: impl ::core::convert::Into<std::io::Error>) -> Self {
+                Piss::DiskError(::core::convert::Into::into(source))
+            }
+
+            #[doc = "Creates a `Piss::Type`."]
+            #[must_use]
+            pub fn r#type() -> Self {
+                Piss::Type
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.constructors().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! # Context
+//!
+//! Makes a `snafu`-style context selector for each of the user's variants,
+//! if they asked for them with `#[error(context_selectors)]`.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{Ident, Index};
+
+use crate::{
+    parser::{
+        field::{WrappedField, WrappedFields},
+        variant::WrappedVariant,
+        UserEnum,
+    },
+    util,
+};
+
+impl UserEnum {
+    /// A selector struct for each variant, named like `ReadSnafu`.
+    ///
+    /// Variants with a source get an `IntoError` implementation, so
+    /// `ContextExt::context` can fill the source in. The others can `build`
+    /// the error (or `fail` with it) right away.
+    ///
+    /// # Errors
+    ///
+    /// The enum can't be generic, since each selector would need its generics.
+    /// A `via` source also has to come from just one type.
+    pub(crate) fn context_selectors(&self) -> syn::Result<Option<TokenStream2>> {
+        if !self.attributes().context_selectors {
+            return Ok(None);
+        }
+
+        if !self.generics().params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                self.generics(),
+                "The `context_selectors` option doesn't support generic enums.",
+            ));
+        }
+
+        let selectors = self
+            .variants()
+            .iter()
+            .map(|v| self.context_selector(v))
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(Some(quote! { #(#selectors)* }))
+    }
+
+    /// The selector struct for one variant, plus its `impl`s.
+    fn context_selector(&self, v: &WrappedVariant) -> syn::Result<TokenStream2> {
+        let enum_ident = self.ident();
+        let vis = self.vis();
+        let selector = format_ident!("{}Snafu", v.ident);
+
+        let fields = Self::selector_variant_fields(v);
+        let source = fields.iter().find(|f| Self::is_selector_source(f));
+
+        // every other field becomes a field on the selector, with its own
+        // generic type, so it can take anything that goes `Into` the field
+        let selector_fields = fields
+            .iter()
+            .filter(|f| !Self::is_selector_source(f))
+            .enumerate()
+            .map(|(i, f)| (format_ident!("__T{}", i), f))
+            .collect::<Vec<_>>();
+        let generics = selector_fields
+            .iter()
+            .map(|field| &field.0)
+            .collect::<Vec<_>>();
+        let bounds = selector_fields.iter().map(|field| {
+            let (ref generic, wrapped) = *field;
+            let ty = &wrapped.info().ty;
+            quote! { #generic: ::core::convert::Into<#ty> }
+        });
+
+        let doc = format!(
+            "A context selector for `{enum_ident}::{}`. See `pisserror::IntoError`.",
+            v.ident
+        );
+        let definition = match v.fields {
+            WrappedFields::Named(_) => {
+                let definitions = selector_fields.iter().map(|field| {
+                    let (ref generic, wrapped) = *field;
+                    let ident = &wrapped.info().ident;
+                    let field_doc = format!("The variant's `{}` field.", quote!(#ident));
+                    quote! {
+                        #[doc = #field_doc]
+                        #vis #ident: #generic
+                    }
+                });
+                quote! { #vis struct #selector<#(#generics),*> { #(#definitions),* } }
+            }
+            WrappedFields::Unnamed(_) if !selector_fields.is_empty() => {
+                quote! { #vis struct #selector<#(#generics),*>(#(#vis #generics),*); }
+            }
+            WrappedFields::Unnamed(_) | WrappedFields::Unit => quote! { #vis struct #selector; },
+        };
+
+        let impls = if let Some(source_field) = source {
+            let (source_ty, source_value) = Self::selector_source(source_field)?;
+            let construct = Self::construct_from_selector(&enum_ident, v, &source_value);
+            quote! {
+                #[automatically_derived]
+                impl<#(#bounds),*> ::pisserror::IntoError<#enum_ident> for #selector<#(#generics),*> {
+                    type Source = #source_ty;
+
+                    fn into_error(self, source: Self::Source) -> #enum_ident {
+                        #construct
+                    }
+                }
+            }
+        } else {
+            // there's no source to fill in
+            let construct = Self::construct_from_selector(&enum_ident, v, &TokenStream2::new());
+            let ok_ty = Ident::new("__T", Span::call_site());
+            quote! {
+                #[automatically_derived]
+                impl<#(#bounds),*> #selector<#(#generics),*> {
+                    /// Makes the error.
+                    #[must_use]
+                    #vis fn build(self) -> #enum_ident {
+                        #construct
+                    }
+
+                    /// Makes the error, wrapped in `Err`.
+                    #vis fn fail<#ok_ty>(self) -> ::core::result::Result<#ok_ty, #enum_ident> {
+                        ::core::result::Result::Err(self.build())
+                    }
+                }
+            }
+        };
+
+        Ok(quote! {
+            #[doc = #doc]
+            #[derive(Clone, Copy, Debug)]
+            #definition
+
+            #impls
+        })
+    }
+
+    /// The type that `context` takes as the source, and how to turn a
+    /// `source` binding of that type into the field.
+    ///
+    /// Optional sources take what's inside the `Option`, and `via` sources
+    /// take whatever `via` converts from, so callers don't wrap them first.
+    fn selector_source(f: &WrappedField) -> syn::Result<(TokenStream2, TokenStream2)> {
+        let info = f.info();
+
+        if let Some(ref via) = info.from_via {
+            let from_ty = match *info.from_types.as_slice() {
+                [] => util::pointee(&info.ty).unwrap_or(&info.ty),
+                [ref only] => only,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        via,
+                        "A context selector can only fill in a `via` source from one type. \
+                        Please list just one, or leave out `context_selectors`.",
+                    ))
+                }
+            };
+            return Ok((quote!(#from_ty), quote!((#via)(source))));
+        }
+
+        if let Some(inner) = util::option_inner(&info.ty) {
+            return Ok((quote!(#inner), quote!(::core::option::Option::Some(source))));
+        }
+
+        let ty = &info.ty;
+        Ok((quote!(#ty), quote!(source)))
+    }
+
+    /// Builds the variant back up from the selector, with the source field
+    /// set to `source_value`.
+    fn construct_from_selector(
+        enum_ident: &Ident,
+        v: &WrappedVariant,
+        source_value: &TokenStream2,
+    ) -> TokenStream2 {
+        let variant_path = v.variant_path(enum_ident.clone());
+        let fields = Self::selector_variant_fields(v);
+
+        let mut selector_index = 0_usize;
+        let values = fields.iter().map(|f| {
+            if Self::is_selector_source(f) {
+                return source_value.clone();
+            }
+
+            let value = if let Some(ref ident) = f.info().ident {
+                quote!(self.#ident)
+            } else {
+                let index = Index::from(selector_index);
+                quote!(self.#index)
+            };
+            selector_index = selector_index.saturating_add(1);
+            quote!(::core::convert::Into::into(#value))
+        });
+
+        match v.fields {
+            WrappedFields::Named(_) => {
+                let idents = fields.iter().map(|f| &f.info().ident);
+                quote! { #variant_path { #(#idents: #values),* } }
+            }
+            WrappedFields::Unnamed(_) => quote! { #variant_path(#(#values),*) },
+            WrappedFields::Unit => quote! { #variant_path },
+        }
+    }
+
+    /// All of the variant's fields, or none for unit variants.
+    fn selector_variant_fields(v: &WrappedVariant) -> &[WrappedField] {
+        match v.fields {
+            WrappedFields::Named(ref fields) | WrappedFields::Unnamed(ref fields) => fields,
+            WrappedFields::Unit => &[],
+        }
+    }
+
+    /// Whether the selector leaves this field out, for `context` to fill in.
+    const fn is_selector_source(f: &WrappedField) -> bool {
+        f.has_from_attribute() || f.info().source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn selectors_leave_out_the_source() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(context_selectors)]
+            pub enum Piss {
+                #[error("couldn't read `{path}`")]
+                Read {
+                    path: String,
+                    #[source]
+                    source: std::io::Error,
+                },
+                #[error("port {_0} is taken")]
+                PortTaken(u16),
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            #[doc = "A context selector for `Piss::Read`. See `pisserror::IntoError`."]
+            #[derive(Clone, Copy, Debug)]
+            pub struct ReadSnafu<__T0> {
+                #[doc = "The variant's `path` field."]
+                pub path: __T0
+            }
+
+            #[automatically_derived]
+            impl<__T0: ::core::convert::Into<String> > ::pisserror::IntoError<Piss> for ReadSnafu<__T0> {
+                type Source = std::io::Error;
+
+                fn into_error(self, source: Self::Source) -> Piss {
+                    Piss::Read {
+                        path: ::core::convert::Into::into(self.path),
+                        source: source
+                    }
+                }
+            }
+
+            #[doc = "A context selector for `Piss::PortTaken`. See `pisserror::IntoError`."]
+            #[derive(Clone, Copy, Debug)]
+            pub struct PortTakenSnafu<__T0>(pub __T0);
+
+            #[automatically_derived]
+            impl<__T0: ::core::convert::Into<u16> > PortTakenSnafu<__T0> {
+                /// Makes the error.
+                #[must_use]
+                pub fn build(self) -> Piss {
+                    Piss::PortTaken(::core::convert::Into::into(self.0))
+                }
+
+                /// Makes the error, wrapped in `Err`.
+                pub fn fail<__T>(self) -> ::core::result::Result<__T, Piss> {
+                    ::core::result::Result::Err(self.build())
+                }
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.context_selectors().unwrap().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn selectors_take_the_unwrapped_source() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(context_selectors)]
+            pub enum Piss {
+                #[error("couldn't fetch `{url}`")]
+                Fetch {
+                    url: String,
+                    #[source]
+                    last_error: Option<std::io::Error>,
+                },
+                #[error(transparent)]
+                Shared(#[from(via = Arc::new)] Arc<std::io::Error>),
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+        let selectors = user_enum.context_selectors().unwrap().unwrap().to_string();
+
+        let expected_impls: [TokenStream2; 2] = [
+            parse_quote! {
+                type Source = std::io::Error;
+
+                fn into_error(self, source: Self::Source) -> Piss {
+                    Piss::Fetch {
+                        url: ::core::convert::Into::into(self.url),
+                        last_error: ::core::option::Option::Some(source)
+                    }
+                }
+            },
+            parse_quote! {
+                type Source = std::io::Error;
+
+                fn into_error(self, source: Self::Source) -> Piss {
+                    Piss::Shared((Arc::new)(source))
+                }
+            },
+        ];
+        for expected in expected_impls {
+            assert!(
+                selectors.contains(&expected.to_string()),
+                "missing: {expected}"
+            );
+        }
+
+        // `via` with a few types doesn't say which one the source is
+        let ambiguous: ItemEnum = parse_quote! {
+            #[error(context_selectors)]
+            pub enum Piss {
+                #[error(transparent)]
+                Parse(#[from(ParseIntError, ParseFloatError, via = Box::new)] Box<dyn Error>),
+            }
+        };
+        let user_enum = UserEnum::new(ambiguous.into()).unwrap();
+        assert!(user_enum.context_selectors().is_err());
+    }
+
+    #[test]
+    fn selectors_need_concrete_enums() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(context_selectors)]
+            enum Piss<T> {
+                #[error("bad value")]
+                Bad { value: T },
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        assert!(user_enum.context_selectors().is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! # Debug
+//!
+//! Implements the `Debug` trait for the user's error type, if they asked for
+//! it with `#[error(debug = ...)]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Path;
+
+use crate::parser::{attr::DebugStyle, field::WrappedFields, UserEnum};
+
+impl UserEnum {
+    /// The `Debug` trait's `fmt` method, if the user asked for one.
+    ///
+    /// Without the alternate flag (`{:?}`), this prints the `Display` message
+    /// (and, for `report`, each source). With it (`{:#?}`), it prints the
+    /// structure of the variant, just like `#[derive(Debug)]` would.
+    pub(crate) fn debug_fmt(&self, error_path: &Path) -> Option<TokenStream2> {
+        let style = self.attributes().debug?;
+
+        let human = match style {
+            DebugStyle::Display => quote! { core::fmt::Display::fmt(self, f) },
+            DebugStyle::Report => quote! {
+                core::fmt::Display::fmt(self, f)?;
+
+                if let Some(first) = #error_path::source(self) {
+                    f.write_str("\n\nCaused by:")?;
+
+                    // only number the causes when there's more than one
+                    let numbered = #error_path::source(first).is_some();
+                    let causes = core::iter::successors(Some(first), |e| #error_path::source(*e));
+
+                    for (i, cause) in causes.enumerate() {
+                        if numbered {
+                            f.write_fmt(format_args!("\n{:>7}: {}", i, cause))?;
+                        } else {
+                            f.write_fmt(format_args!("\n      {}", cause))?;
+                        }
+                    }
+                }
+
+                Ok(())
+            },
+        };
+
+        let structural = self.structural_debug_fmt();
+
+        Some(quote! {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                if f.alternate() {
+                    #structural
+                } else {
+                    #human
+                }
+            }
+        })
+    }
+
+    /// Prints out the variant's structure, like `#[derive(Debug)]`.
+    fn structural_debug_fmt(&self) -> TokenStream2 {
+        if self.variants().is_empty() {
+            // same as `Display`: this keeps the empty `match` happy
+            return quote! { match *self { _ => Ok(()), } };
+        }
+
+        let match_arms = self.variants().iter().map(|v| {
+            let match_head = v.filled_match_head(self.ident(), true);
+            let name = v.ident.to_string();
+
+            let body = match v.fields {
+                WrappedFields::Named(ref fields) => {
+                    let field_calls = fields.iter().map(|field| {
+                        let ident = &field.info().ident;
+                        let field_name = ident.as_ref().map(ToString::to_string);
+                        quote! { .field(#field_name, #ident) }
+                    });
+                    quote! { f.debug_struct(#name) #(#field_calls)* .finish() }
+                }
+                WrappedFields::Unnamed(ref fields) => {
+                    let field_calls = (0..fields.len()).map(|i| {
+                        let ident = quote::format_ident!("_{}", i);
+                        quote! { .field(#ident) }
+                    });
+                    quote! { f.debug_tuple(#name) #(#field_calls)* .finish() }
+                }
+                WrappedFields::Unit => quote! { f.write_str(#name) },
+            };
+
+            quote! { #match_head => { #body } }
+        });
+
+        quote! {
+            match *self {
+                #(#match_arms),*
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! # Defmt
+//!
+//! Implements `defmt::Format` for the user's error type, if they asked for it
+//! with `#[error(defmt)]`. Messages are translated into `defmt`'s format
+//! syntax.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::LitStr;
+
+use crate::parser::{
+    attr::ErrorAttribute,
+    format::{Message, Piece},
+    UserEnum,
+};
+
+impl UserEnum {
+    /// The `defmt::Format` trait's `format` method, if the user asked for one.
+    ///
+    /// This is opt-in, even with the `defmt` feature on. Otherwise, turning on
+    /// the feature anywhere would break every error with a field that isn't
+    /// `Format`. Also, `defmt`'s macros look for a crate named `defmt`, so the
+    /// user's crate needs to depend on it.
+    ///
+    /// # Errors
+    ///
+    /// Each message can only use format specs that `defmt` can express.
+    pub(crate) fn defmt_format(&self) -> syn::Result<Option<TokenStream2>> {
+        if !self.attributes().defmt {
+            return Ok(None);
+        }
+
+        let mut match_arms = self
+            .variants()
+            .iter()
+            .map(|v| {
+                let match_head = v.filled_match_head(self.ident(), true);
+
+                let body = match v.error_attribute {
+                    ErrorAttribute::Stringy(ref tokens) => {
+                        let message = Message::parse(tokens)?;
+                        let (format_str, args) = Self::defmt_message(&message)?;
+                        quote! { defmt::write!(f, #format_str #(, #args)*) }
+                    }
+
+                    ErrorAttribute::Transparent => {
+                        let from_field = v.first_field_binding();
+                        quote! { defmt::Format::format(#from_field, f) }
+                    }
+
+                    ErrorAttribute::Many => {
+                        let many_field = v.first_field_binding();
+
+                        quote! {
+                            for (i, e) in core::iter::IntoIterator::into_iter(#many_field).enumerate() {
+                                if i != 0 {
+                                    defmt::write!(f, "\n");
+                                }
+                                defmt::write!(f, "{}. {}", i.saturating_add(1), e);
+                            }
+                        }
+                    }
+                };
+
+                Ok(quote! { #match_head => { #body } })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        // like `Display`, an enum without variants still needs an arm
+        if match_arms.is_empty() {
+            match_arms.push(quote! { _ => {} });
+        }
+
+        Ok(Some(quote! {
+            fn format(&self, f: defmt::Formatter<'_>) {
+                match *self {
+                    #(#match_arms),*
+                }
+            }
+        }))
+    }
+
+    /// Translates a message into a `defmt` format string and its arguments.
+    fn defmt_message(message: &Message) -> syn::Result<(LitStr, Vec<&TokenStream2>)> {
+        let mut format_str = String::new();
+        let mut args = Vec::new();
+
+        for piece in &message.pieces {
+            match *piece {
+                Piece::Text(ref text) => {
+                    format_str.push_str(&text.replace('{', "{{").replace('}', "}}"));
+                }
+                Piece::Argument {
+                    ref value,
+                    ref spec,
+                } => {
+                    // `defmt` only has a few display hints, and no padding
+                    let hint = match spec.as_str() {
+                        "" => "",
+                        "?" => ":?",
+                        "x" => ":x",
+                        "X" => ":X",
+                        "#x" => ":#x",
+                        "#X" => ":#X",
+                        "b" => ":b",
+                        "#b" => ":#b",
+                        "o" => ":o",
+                        "#o" => ":#o",
+                        _ => return Err(Self::err_unsupported_defmt_spec(&message.lit, spec)),
+                    };
+
+                    format_str.push('{');
+                    format_str.push_str(hint);
+                    format_str.push('}');
+                    args.push(value);
+                }
+            }
+        }
+
+        Ok((LitStr::new(&format_str, message.lit.span()), args))
+    }
+
+    fn err_unsupported_defmt_spec(lit: &LitStr, spec: &str) -> syn::Error {
+        syn::Error::new_spanned(
+            lit,
+            format!(
+                "`defmt` can't express the format spec `{{:{spec}}}`. It only supports \
+                `{{}}`, `{{:?}}`, and hex, binary, or octal hints like `{{:#x}}`."
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn defmt_translates_messages() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(defmt)]
+            enum Piss {
+                #[error("bad {{header}}: expected {expected:#x}, got {:?}", got)]
+                Header { expected: u8, got: u8 },
+                #[error(transparent)]
+                Other(#[from] OtherError),
+            }
+        };
+        if !cfg!(feature = "defmt") {
+            assert!(UserEnum::new(sauce.into()).is_err());
+            return;
+        }
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn format(&self, f: defmt::Formatter<'_>) {
+                match *self {
+                    Piss::Header { ref expected, ref got } => {
+                        defmt::write!(f, "bad {{header}}: expected {:#x}, got {:?}", expected, got)
+                    },
+                    Piss::Other(ref _0) => { defmt::Format::format(_0, f) }
+                }
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.defmt_format().unwrap().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn defmt_rejects_padding() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(defmt)]
+            enum Piss {
+                #[error("the value is {_0:>8}")]
+                Padded(u32),
+            }
+        };
+
+        if cfg!(feature = "defmt") {
+            let user_enum = UserEnum::new(sauce.into()).unwrap();
+            assert!(user_enum.defmt_format().is_err());
+        }
+    }
+}
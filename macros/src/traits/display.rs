@@ -5,7 +5,7 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 
-use crate::parser::{attr::ErrorAttribute, UserEnum};
+use crate::parser::{attr::ErrorAttribute, field::WrappedFields, UserEnum};
 
 impl UserEnum {
     /// The `Display` trait's `fmt` method.
@@ -31,12 +31,12 @@ impl UserEnum {
             self.variants()
                 .iter()
                 .map(|v| {
-                    let match_head = v.filled_match_head(self.ident());
+                    let match_head = v.filled_match_head(self.ident(), true);
 
                     // make the match arm
                     match v.error_attribute {
                         ErrorAttribute::Stringy(ref format_args_str) => {
-                            quote! { #match_head => {f.write_str(format!(#format_args_str).as_str())} }
+                            quote! { #match_head => {f.write_fmt(format_args!(#format_args_str))} }
                         }
 
                         ErrorAttribute::Transparent => {
@@ -48,8 +48,32 @@ impl UserEnum {
                                 .ident;
 
                             // check if we even have an ident
-                            let format_args_str = from_field_ident.map_or_else(|| quote!(&_0.to_string()), |ident| quote!(&#ident.to_string()));
-                            quote! { #match_head => { f.write_str(#format_args_str) }}
+                            let from_field =
+                                from_field_ident.map_or_else(|| quote!(_0), |ident| quote!(#ident));
+                            quote! { #match_head => { core::fmt::Display::fmt(#from_field, f) }}
+                        }
+
+                        ErrorAttribute::Many => {
+                            // a `many` variant has exactly one field. let's print each item in it
+                            let many_field = match v.fields {
+                                WrappedFields::Named(ref fields) => {
+                                    let ident = fields
+                                        .first()
+                                        .and_then(|field| field.info().ident.clone());
+                                    quote!(#ident)
+                                }
+                                WrappedFields::Unnamed(_) | WrappedFields::Unit => quote!(_0),
+                            };
+
+                            quote! { #match_head => {
+                                for (i, e) in core::iter::IntoIterator::into_iter(#many_field).enumerate() {
+                                    if i != 0 {
+                                        f.write_str("\n")?;
+                                    }
+                                    f.write_fmt(format_args!("{}. {}", i.saturating_add(1), e))?;
+                                }
+                                Ok(())
+                            }}
                         }
                     }
                 })
@@ -71,3 +95,42 @@ impl UserEnum {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    /// `Display` shouldn't need an allocator, so `no_std` users without one
+    /// can still use the derive.
+    #[test]
+    fn fmt_shouldnt_allocate() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("the number is {_0}")]
+                Stringy(u8),
+                #[error(transparent)]
+                Transparent(#[from] core::fmt::Error),
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+                match *self {
+                    Piss::Stringy(ref _0) => { f.write_fmt(format_args!("the number is {_0}")) },
+                    Piss::Transparent(ref _0) => { core::fmt::Display::fmt(_0, f) }
+                }
+            }
+        };
+        // the error path depends on the `std` feature
+        let got = user_enum
+            .fmt()
+            .to_string()
+            .replace("std :: fmt :: Error", "core :: fmt :: Error");
+
+        assert_eq!(expected.to_string(), got);
+    }
+}
@@ -4,27 +4,58 @@
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
+use syn::Type;
 
-use crate::parser::{field::WrappedFields, UserEnum};
+use crate::{
+    parser::{attr::ErrorAttribute, field::WrappedFields, UserEnum},
+    util,
+};
 
 impl UserEnum {
     /// The `Error` trait's `source` method.
     pub(crate) fn source(&self) -> TokenStream2 {
         let match_arms = self.variants().iter().map(|v| {
-            if let Some(ref info) = v.from_attribute {
+            // a `many` variant holds a list of errors, which isn't one source
+            let from_attribute = match v.error_attribute {
+                ErrorAttribute::Many => None,
+                ErrorAttribute::Stringy(_) | ErrorAttribute::Transparent => {
+                    v.from_attribute.as_ref()
+                }
+            };
+
+            // fields marked `#[source]` are sources too, but not `From`s
+            let source_field = match v.error_attribute {
+                ErrorAttribute::Many => None,
+                ErrorAttribute::Stringy(_) | ErrorAttribute::Transparent => v.source_field(),
+            };
+
+            if let Some(info) = from_attribute {
                 let variant_path = v.variant_path(self.ident());
+                let deref = Self::source_deref(&info.ty);
 
                 match v.fields {
                     WrappedFields::Named(_) => {
                         let from_ident = info.ident.clone().unwrap();
-                        quote! { #variant_path { ref #from_ident } => Some(#from_ident)}
+                        quote! { #variant_path { ref #from_ident } => Some(#deref #from_ident)}
                         // named_arm
                     }
-                    WrappedFields::Unnamed(_) => quote! { #variant_path(ref e) => Some(e) },
+                    WrappedFields::Unnamed(_) => quote! { #variant_path(ref e) => Some(#deref e) },
                     WrappedFields::Unit => {
                         unreachable!("unit enums cannot have a #[from] field")
                     }
                 }
+            } else if let Some(info) = source_field {
+                let (match_head, bound) = v.partial_match_head(self.ident(), |f| f.info().source);
+                let binding = bound.first().map(|bound_field| &bound_field.0);
+
+                // optional sources might not be there
+                if let Some(inner) = util::option_inner(&info.ty) {
+                    let deref = Self::source_deref(inner);
+                    quote! { #match_head => #binding.as_ref().map(|e| #deref e as _) }
+                } else {
+                    let deref = Self::source_deref(&info.ty);
+                    quote! { #match_head => Some(#deref #binding) }
+                }
             } else {
                 let left_side = v.match_head(self.ident());
                 quote! {#left_side => None}
@@ -40,6 +71,20 @@ impl UserEnum {
         }
     }
 
+    /// Anything needed to turn a reference to the source field into a
+    /// reference to the source.
+    ///
+    /// Trait objects in a `Box`, `Arc`, or `Rc` don't implement `Error`, so we
+    /// need to look inside the pointer. Other errors in one, like
+    /// `Arc<io::Error>`, should be the error itself, so they can be downcast.
+    fn source_deref(ty: &Type) -> TokenStream2 {
+        if util::pointee(ty).is_some() {
+            quote!(&**)
+        } else {
+            quote!()
+        }
+    }
+
     /// The `Error` trait's `descritpion` method.
     ///
     /// The method this generates is deprecated in favor of `Display`/`ToString`
@@ -105,6 +150,120 @@ mod tests {
         assert_eq!(expected.to_string(), got.to_string());
     }
 
+    #[test]
+    fn source_should_use_marked_fields() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("couldn't read `{path}`")]
+                Read {
+                    path: String,
+                    #[source]
+                    cause: std::io::Error,
+                },
+                #[error("bad token")]
+                BadToken(char, #[source] std::fmt::Error),
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                match *self {
+                    Piss::Read { ref cause, .. } => Some(cause),
+                    Piss::BadToken(_, ref _1) => Some(_1)
+                }
+            }
+        };
+        let got = user_enum.source();
+
+        assert_eq!(expected.to_string(), got.to_string());
+
+        let two_sources: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("two causes")]
+                Two(#[source] std::io::Error, #[source] std::fmt::Error),
+            }
+        };
+        assert!(UserEnum::new(two_sources.into()).is_err());
+    }
+
+    #[test]
+    fn source_should_allow_optional_fields() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("timed out after {after:?}")]
+                Timeout {
+                    after: std::time::Duration,
+                    #[source]
+                    last_error: Option<std::io::Error>,
+                },
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                match *self {
+                    Piss::Timeout { ref last_error, .. } => last_error.as_ref().map(|e| e as _)
+                }
+            }
+        };
+        let got = user_enum.source();
+
+        assert_eq!(expected.to_string(), got.to_string());
+
+        // `From` can't make an optional source
+        let from_option: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error(transparent)]
+                Io(#[from] Option<std::io::Error>),
+            }
+        };
+        assert!(UserEnum::new(from_option.into()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn source_should_unbox_trait_objects() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("boxed")]
+                Boxed(#[from] Box<dyn std::error::Error + Send + Sync>),
+                #[error("not a trait object")]
+                BoxedConcrete {
+                    #[from]
+                    inner: Box<std::io::Error>,
+                },
+                #[error("shared")]
+                Shared(#[from(via = std::sync::Arc::new)] std::sync::Arc<std::fmt::Error>),
+                #[error("shared trait object")]
+                SharedDyn(#[from] std::sync::Arc<dyn std::error::Error + Send + Sync>),
+                #[error("counted trait object")]
+                Counted {
+                    path: String,
+                    #[source]
+                    cause: std::rc::Rc<dyn std::error::Error>,
+                },
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                match *self {
+                    Piss::Boxed(ref e) => Some(&**e),
+                    Piss::BoxedConcrete { ref inner } => Some(&**inner),
+                    Piss::Shared(ref e) => Some(&**e),
+                    Piss::SharedDyn(ref e) => Some(&**e),
+                    Piss::Counted { ref cause, .. } => Some(&**cause)
+                }
+            }
+        };
+        let got = user_enum.source();
+
+        assert_eq!(expected.to_string(), got.to_string());
+    }
+
     #[test]
     fn description_shouldnt_change() {
         let expected: TokenStream2 = parse_quote! {
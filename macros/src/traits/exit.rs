@@ -0,0 +1,93 @@
+//! # Exit
+//!
+//! Implements `pisserror::ProcessExit` for the user's error type, if the enum
+//! or any of its variants has an `exit_code` option.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::parser::UserEnum;
+
+impl UserEnum {
+    /// The `ProcessExit` trait's `exit_code` method, if any `exit_code`
+    /// options were given.
+    ///
+    /// Variants without an `exit_code` use the enum's, or `1` if it doesn't
+    /// have one either.
+    pub(crate) fn exit_code(&self) -> Option<TokenStream2> {
+        let has_exit_code = self.attributes().exit_code.is_some()
+            || self
+                .variants()
+                .iter()
+                .any(|v| v.options.exit_code.is_some());
+        if !has_exit_code {
+            return None;
+        }
+
+        // a general failure
+        let default = self.attributes().exit_code.unwrap_or(1);
+
+        let match_arms = self.variants().iter().map(|v| {
+            let match_head = v.match_head(self.ident());
+            let exit_code = v.options.exit_code.unwrap_or(default);
+            quote! { #match_head => #exit_code }
+        });
+
+        Some(quote! {
+            fn exit_code(&self) -> u8 {
+                match *self {
+                    #(#match_arms),*
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn exit_code_uses_defaults() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("couldn't write")]
+                #[error(exit_code = 74)]
+                Write(#[from] std::io::Error),
+                #[error("other")]
+                Other { reason: String },
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn exit_code(&self) -> u8 {
+                match *self {
+                    Piss::Write(..) => 74u8,
+                    Piss::Other { .. } => 1u8
+                }
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.exit_code().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn zero_exit_code_is_rejected() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(exit_code = 0)]
+            enum Piss {
+                #[error("hi")]
+                Variant,
+            }
+        };
+
+        assert!(UserEnum::new(sauce.into()).is_err());
+    }
+}
@@ -5,7 +5,10 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 
-use crate::parser::{field::WrappedFields, UserEnum};
+use crate::{
+    parser::{field::WrappedFields, UserEnum},
+    util,
+};
 
 impl UserEnum {
     /// Returns ALL `From` implementations for the `#[from]` variants of the
@@ -15,36 +18,62 @@ impl UserEnum {
 
         let (impl_generics, type_generics, where_clause) = self.generics().split_for_impl();
 
-        // create a new `From<external::Error> for UserError` for each from variant
+        // create a new `From<external::Error> for UserError` for each from variant,
+        // or for each type it lists
         let from_impls = self
             .variants()
             .iter()
             .filter(|v| v.from_attribute.is_some())
-            .map(|from_v| {
+            .flat_map(|from_v| {
                 let variant_ident = from_v.ident.clone();
                 let from_attr = from_v.from_attribute.clone().unwrap();
-                let from_type = from_attr.ty;
 
-                // let's decide which style to use during construction
-                let style = match from_v.fields {
-                    WrappedFields::Named(_) => {
-                        let from_ident = from_attr.ident.unwrap();
-                        quote!(#enum_ident::#variant_ident {#from_ident: value})
+                // listed types go `Into` the field's type, unless there's a
+                // `via` to convert them. without a list, `via` converts from
+                // whatever the field's pointer holds
+                let from_types = match (from_attr.from_types.is_empty(), from_attr.from_via) {
+                    (true, None) => vec![(from_attr.ty, quote!(value))],
+                    (false, None) => from_attr
+                        .from_types
+                        .into_iter()
+                        .map(|ty| (ty, quote!(::core::convert::Into::into(value))))
+                        .collect(),
+                    (listed, Some(via)) => {
+                        let types = if listed {
+                            util::pointee(&from_attr.ty).cloned().into_iter().collect()
+                        } else {
+                            from_attr.from_types
+                        };
+                        types
+                            .into_iter()
+                            .map(|ty| (ty, quote!((#via)(value))))
+                            .collect()
                     }
-                    WrappedFields::Unnamed(_) => {
-                        quote!(#enum_ident::#variant_ident(value))
-                    }
-                    WrappedFields::Unit => unreachable!(),
                 };
 
-                quote! {
-                    #[automatically_derived]
-                    impl #impl_generics core::convert::From<#from_type> for #enum_ident #type_generics #where_clause {
-                        fn from(value: #from_type) -> Self {
-                            #style
+                let from_ident = from_attr.ident;
+                from_types.into_iter().map(|(from_type, value)| {
+                    // let's decide which style to use during construction
+                    let style = match from_v.fields {
+                        WrappedFields::Named(_) => {
+                            quote!(#enum_ident::#variant_ident {#from_ident: #value})
+                        }
+                        WrappedFields::Unnamed(_) => {
+                            quote!(#enum_ident::#variant_ident(#value))
+                        }
+                        WrappedFields::Unit => unreachable!(),
+                    };
+
+                    quote! {
+                        #[automatically_derived]
+                        impl #impl_generics core::convert::From<#from_type> for #enum_ident #type_generics #where_clause {
+                            fn from(value: #from_type) -> Self {
+                                #style
+                            }
                         }
                     }
-                }
+                })
+                .collect::<Vec<_>>()
             });
 
         quote! {
@@ -126,6 +155,101 @@ mod tests {
         assert_eq!(user_enum.from().to_string(), expected.to_string());
     }
 
+    #[test]
+    fn listed_types_each_get_a_from() {
+        let sauce: ItemEnum = parse_quote! {
+            enum MyError {
+                #[error(transparent)]
+                Parse(
+                    #[from(core::num::ParseIntError, core::num::ParseFloatError)]
+                    Box<dyn std::error::Error + Send + Sync>
+                ),
+            }
+        };
+
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            #[automatically_derived]
+            impl core::convert::From<core::num::ParseIntError> for MyError {
+                fn from(value: core::num::ParseIntError) -> Self {
+                    MyError::Parse(::core::convert::Into::into(value))
+                }
+            }
+            #[automatically_derived]
+            impl core::convert::From<core::num::ParseFloatError> for MyError {
+                fn from(value: core::num::ParseFloatError) -> Self {
+                    MyError::Parse(::core::convert::Into::into(value))
+                }
+            }
+        };
+
+        assert_eq!(user_enum.from().to_string(), expected.to_string());
+
+        // the list can't be empty
+        let empty: ItemEnum = parse_quote! {
+            enum MyError {
+                #[error(transparent)]
+                Parse(#[from()] std::io::Error),
+            }
+        };
+        assert!(UserEnum::new(empty.into()).is_err());
+    }
+
+    #[test]
+    fn via_converts_into_the_pointer() {
+        let sauce: ItemEnum = parse_quote! {
+            enum MyError {
+                #[error(transparent)]
+                Io(#[from(via = Arc::new)] Arc<std::io::Error>),
+                #[error(transparent)]
+                Fmt {
+                    #[from(core::fmt::Error, via = |e| Rc::new(Wrapper(e)))]
+                    inner: Rc<Wrapper>,
+                },
+            }
+        };
+
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            #[automatically_derived]
+            impl core::convert::From<std::io::Error> for MyError {
+                fn from(value: std::io::Error) -> Self {
+                    MyError::Io((Arc::new)(value))
+                }
+            }
+            #[automatically_derived]
+            impl core::convert::From<core::fmt::Error> for MyError {
+                fn from(value: core::fmt::Error) -> Self {
+                    MyError::Fmt {
+                        inner: (|e| Rc::new(Wrapper(e)))(value)
+                    }
+                }
+            }
+        };
+
+        assert_eq!(user_enum.from().to_string(), expected.to_string());
+
+        // `via` can't guess what a plain field converts from
+        let unguessable: ItemEnum = parse_quote! {
+            enum MyError {
+                #[error(transparent)]
+                Io(#[from(via = Wrapper)] Wrapper),
+            }
+        };
+        assert!(UserEnum::new(unguessable.into()).is_err());
+
+        // ...or a trait object, which can't be passed by value
+        let unsized_pointee: ItemEnum = parse_quote! {
+            enum MyError {
+                #[error(transparent)]
+                Shared(#[from(via = Arc::new)] Arc<dyn Error + Send + Sync>),
+            }
+        };
+        assert!(UserEnum::new(unsized_pointee.into()).is_err());
+    }
+
     #[test]
     fn errs_with_multiple_fields() {
         // make the enum
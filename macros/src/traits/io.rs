@@ -0,0 +1,135 @@
+//! # Io
+//!
+//! Implements `From<UserError> for std::io::Error`, if the enum or any of its
+//! variants has an `io_kind` option.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::{
+    parser::{attr::ErrorAttribute, UserEnum},
+    util,
+};
+
+impl UserEnum {
+    /// A `From` implementation turning the user's error into an
+    /// `std::io::Error`, if any `io_kind` options were given.
+    ///
+    /// The `io::Error` holds onto the user's error, so it can be grabbed
+    /// again with `get_ref` or `into_inner`. The only exception is
+    /// `transparent` variants around an `io::Error`, which just hand it back.
+    pub(crate) fn io_error_from(&self) -> Option<TokenStream2> {
+        let has_io_kind = self.attributes().io_kind.is_some()
+            || self.variants().iter().any(|v| v.options.io_kind.is_some());
+        if !has_io_kind {
+            return None;
+        }
+
+        let enum_ident = self.ident();
+        let default = self.attributes().io_kind.as_ref().map_or_else(
+            || quote!(std::io::ErrorKind::Other),
+            |kind| quote!(std::io::ErrorKind::#kind),
+        );
+
+        let match_arms = self.variants().iter().map(|v| {
+            let unwrappable = matches!(v.error_attribute, ErrorAttribute::Transparent)
+                && v.from_attribute
+                    .as_ref()
+                    .map_or(false, |f| util::is_io_error(&f.ty));
+
+            if unwrappable {
+                // take the inner error out, since we own the user's error. the
+                // type only looks like an `io::Error`, so convert it to be sure
+                if let Some((match_head, ident)) = v.match_head_with_from(enum_ident.clone(), false)
+                {
+                    return quote! {
+                        #match_head => return core::convert::From::from(#ident)
+                    };
+                }
+            }
+
+            let match_head = v.match_head(enum_ident.clone());
+            let kind = v
+                .options
+                .io_kind
+                .as_ref()
+                .map_or_else(|| default.clone(), |kind| quote!(std::io::ErrorKind::#kind));
+            quote! { #match_head => #kind }
+        });
+
+        // `io::Error` needs to hold onto the user's error
+        let mut generics = self.generics().clone();
+        let (impl_generics, type_generics, _) = self.generics().split_for_impl();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#enum_ident #type_generics: core::marker::Send + core::marker::Sync + 'static));
+        let where_clause = &generics.where_clause;
+
+        Some(quote! {
+            #[automatically_derived]
+            impl #impl_generics core::convert::From<#enum_ident #type_generics> for std::io::Error #where_clause {
+                fn from(error: #enum_ident #type_generics) -> Self {
+                    let kind = match error {
+                        #(#match_arms),*
+                    };
+
+                    std::io::Error::new(kind, error)
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn into_io_error_unwraps_transparent_io_errors() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("no such key: {_0}")]
+                #[error(io_kind = NotFound)]
+                NoKey(String),
+                #[error(transparent)]
+                Io(#[from] std::io::Error),
+                #[error("disk error")]
+                Disk { #[from] inner: io::Error },
+            }
+        };
+
+        if !cfg!(feature = "std") {
+            assert!(UserEnum::new(sauce.into()).is_err());
+            return;
+        }
+
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            #[automatically_derived]
+            impl core::convert::From<Piss> for std::io::Error
+            where
+                Piss: core::marker::Send + core::marker::Sync + 'static
+            {
+                fn from(error: Piss) -> Self {
+                    let kind = match error {
+                        Piss::NoKey(..) => std::io::ErrorKind::NotFound,
+                        Piss::Io(e) => return core::convert::From::from(e),
+                        Piss::Disk { .. } => std::io::ErrorKind::Other
+                    };
+
+                    std::io::Error::new(kind, error)
+                }
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.io_error_from().unwrap().to_string()
+        );
+    }
+}
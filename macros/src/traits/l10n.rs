@@ -0,0 +1,167 @@
+//! # L10n
+//!
+//! Makes the `localize` method and `MESSAGE_IDS` list for the user's error
+//! type, if any of its variants use the `l10n` option.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::parser::UserEnum;
+
+impl UserEnum {
+    /// A `localize` method, which looks up each variant's `l10n` message in a
+    /// `pisserror::MessageSource`, and a `MESSAGE_IDS` list of those IDs.
+    ///
+    /// Variants without an `l10n` option, or whose message is missing from
+    /// the catalog, fall back to `Display`.
+    pub(crate) fn localize(&self) -> Option<TokenStream2> {
+        let mut ids = Vec::new();
+        for v in self.variants() {
+            if let Some(ref id) = v.options.l10n {
+                if !ids
+                    .iter()
+                    .any(|seen: &syn::LitStr| seen.value() == id.value())
+                {
+                    ids.push(id.clone());
+                }
+            }
+        }
+
+        // the option can't be given without the `i18n` feature
+        if ids.is_empty() {
+            return None;
+        }
+
+        let match_arms = self.variants().iter().map(|v| {
+            let Some(ref id) = v.options.l10n else {
+                let match_head = v.match_head(self.ident());
+                return quote! { #match_head => ::core::option::Option::None };
+            };
+
+            let (match_head, bound) =
+                v.partial_match_head(self.ident(), |field| !field.info().skip_l10n);
+
+            // unnamed fields go by their bindings, since catalogs want names
+            let args = bound.iter().map(|bound_field| {
+                let binding = &bound_field.0;
+                let name = if bound_field.1.starts_with(|c: char| c.is_ascii_digit()) {
+                    format!("_{}", bound_field.1)
+                } else {
+                    bound_field.1.clone()
+                };
+
+                quote! { (#name, #binding as &dyn ::core::fmt::Display) }
+            });
+
+            quote! {
+                #match_head => ::pisserror::MessageSource::message(bundle, #id, &[#(#args),*])
+            }
+        });
+
+        Some(quote! {
+            /// The IDs of every localized message this error uses, from the
+            /// variants' `l10n` options.
+            pub const MESSAGE_IDS: &'static [&'static str] = &[#(#ids),*];
+
+            /// Formats this error using the message in `bundle`, passing each
+            /// field as a named argument. Falls back to this error's usual
+            /// message if the variant isn't localized, or if `bundle` doesn't
+            /// have its message.
+            #[must_use]
+            pub fn localize(
+                &self,
+                bundle: &impl ::pisserror::MessageSource,
+            ) -> ::pisserror::__private::String {
+                let localized = match *self {
+                    #(#match_arms),*
+                };
+
+                localized.unwrap_or_else(|| ::pisserror::__private::ToString::to_string(self))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn localize_passes_named_fields() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("no value for `{key}`")]
+                #[error(l10n = "db-key-not-found")]
+                KeyNotFound {
+                    key: String,
+                    #[error(skip_l10n)]
+                    secret: String,
+                },
+                #[error("table {_0} is locked")]
+                #[error(l10n = "db-locked")]
+                Locked(u32),
+                #[error("plain")]
+                Plain,
+            }
+        };
+
+        if !cfg!(feature = "i18n") {
+            assert!(UserEnum::new(sauce.into()).is_err());
+            return;
+        }
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            /// The IDs of every localized message this error uses, from the
+            /// variants' `l10n` options.
+            pub const MESSAGE_IDS: &'static [&'static str] = &["db-key-not-found", "db-locked"];
+
+            /// Formats this error using the message in `bundle`, passing each
+            /// field as a named argument. Falls back to this error's usual
+            /// message if the variant isn't localized, or if `bundle` doesn't
+            /// have its message.
+            #[must_use]
+            pub fn localize(
+                &self,
+                bundle: &impl ::pisserror::MessageSource,
+            ) -> ::pisserror::__private::String {
+                let localized = match *self {
+                    Piss::KeyNotFound { ref key, .. } => ::pisserror::MessageSource::message(
+                        bundle,
+                        "db-key-not-found",
+                        &[("key", key as &dyn ::core::fmt::Display)]
+                    ),
+                    Piss::Locked(ref _0) => ::pisserror::MessageSource::message(
+                        bundle,
+                        "db-locked",
+                        &[("_0", _0 as &dyn ::core::fmt::Display)]
+                    ),
+                    Piss::Plain => ::core::option::Option::None
+                };
+
+                localized.unwrap_or_else(|| ::pisserror::__private::ToString::to_string(self))
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.localize().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn no_l10n_no_localize() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("plain")]
+                Plain,
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        assert!(user_enum.localize().is_none());
+    }
+}
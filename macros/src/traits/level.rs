@@ -0,0 +1,93 @@
+//! # Level
+//!
+//! Implements pisserror's `Severity` trait for the user's error type, using
+//! the `level` options.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::Ident;
+
+use crate::parser::UserEnum;
+
+impl UserEnum {
+    /// The `Severity` trait's `level` method.
+    ///
+    /// This is made when the enum or any variant has a `level` option, or
+    /// when the enum has `#[error(trace)]`, since `trace_err` records at each
+    /// error's level.
+    pub(crate) fn level(&self) -> Option<TokenStream2> {
+        let has_level = self.attributes().level.is_some()
+            || self.variants().iter().any(|v| v.options.level.is_some());
+        if !has_level && !self.attributes().trace {
+            return None;
+        }
+
+        let enum_ident = self.ident();
+        let default = self
+            .attributes()
+            .level
+            .clone()
+            .unwrap_or_else(|| Ident::new("Error", Span::call_site()));
+
+        let match_arms = self.variants().iter().map(|v| {
+            let match_head = v.match_head(enum_ident.clone());
+            let level = v.options.level.as_ref().unwrap_or(&default);
+            quote! { #match_head => ::pisserror::Level::#level }
+        });
+
+        Some(quote! {
+            fn level(&self) -> ::pisserror::Level {
+                match *self {
+                    #(#match_arms),*
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn level_uses_enum_default() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(level = warn)]
+            enum Piss {
+                #[error("cache miss for `{_0}`")]
+                #[error(level = debug)]
+                CacheMiss(String),
+                #[error("the disk is full")]
+                DiskFull,
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn level(&self) -> ::pisserror::Level {
+                match *self {
+                    Piss::CacheMiss(..) => ::pisserror::Level::Debug,
+                    Piss::DiskFull => ::pisserror::Level::Warn
+                }
+            }
+        };
+
+        assert_eq!(expected.to_string(), user_enum.level().unwrap().to_string());
+    }
+
+    #[test]
+    fn level_must_be_known() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("oops")]
+                #[error(level = catastrophic)]
+                Oops,
+            }
+        };
+
+        assert!(UserEnum::new(sauce.into()).is_err());
+    }
+}
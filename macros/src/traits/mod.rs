@@ -4,14 +4,31 @@
 //! uses the implementation for each trait.
 
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote_spanned;
+use quote::{quote, quote_spanned};
 
 use crate::{parser::UserEnum, util};
 
+mod accessors;
+mod catalog;
+mod constructors;
+mod context;
+mod debug;
+mod defmt;
 mod display;
 mod error;
+mod exit;
 mod from;
+mod io;
+mod l10n;
+mod level;
+mod problem;
+mod raw;
+mod serialize;
+mod status;
+mod trace;
+mod ufmt;
 
+#[allow(clippy::too_many_lines)] // one block for each trait we might implement
 pub(crate) fn derive_error(user_enum: &UserEnum) -> syn::Result<TokenStream2> {
     // make a from block for each variant
     let froms = user_enum.from();
@@ -30,6 +47,7 @@ pub(crate) fn derive_error(user_enum: &UserEnum) -> syn::Result<TokenStream2> {
         util::create_path(user_enum.span(), &["core", "error", "Error"])
     };
     let display_path = util::create_path(user_enum.span(), &["core", "fmt", "Display"]);
+    let debug_path = util::create_path(user_enum.span(), &["core", "fmt", "Debug"]);
 
     // grab generics to ensure our signature matches the enum
     let (impl_generics, type_generics, where_clause) = user_enum.generics().split_for_impl();
@@ -38,6 +56,132 @@ pub(crate) fn derive_error(user_enum: &UserEnum) -> syn::Result<TokenStream2> {
     let enum_ident = user_enum.ident();
     let after_span = user_enum.after_span();
 
+    // and `Debug`, if the user wants us to handle it
+    let debug = user_enum.debug_fmt(&error_path).map(|debug_fmt| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #debug_path for #enum_ident #type_generics #where_clause {
+                #debug_fmt
+            }
+        }
+    });
+
+    // and `Serialize`, if the user asked for that too
+    let serialize = user_enum.serialize(&error_path).map(|serialize| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::pisserror::__private::serde::Serialize
+                for #enum_ident #type_generics #where_clause
+            {
+                #serialize
+            }
+        }
+    });
+
+    // and `HttpStatus`, if there were any `status` options
+    let http_status = user_enum.status_code().map(|status_code| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::pisserror::HttpStatus for #enum_ident #type_generics #where_clause {
+                #status_code
+            }
+        }
+    });
+
+    // and `ProcessExit`, if there were any `exit_code` options
+    let process_exit = user_enum.exit_code().map(|exit_code| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::pisserror::ProcessExit for #enum_ident #type_generics #where_clause {
+                #exit_code
+            }
+        }
+    });
+
+    // and `Severity`, if there were any `level` options
+    let severity = user_enum.level().map(|level| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::pisserror::Severity for #enum_ident #type_generics #where_clause {
+                #level
+            }
+        }
+    });
+
+    // and `defmt::Format`, if the user asked for it
+    let defmt_format = user_enum.defmt_format()?.map(|format| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics defmt::Format for #enum_ident #type_generics #where_clause {
+                #format
+            }
+        }
+    });
+
+    // and `ufmt`'s traits, if the user asked for those
+    let ufmt_display = user_enum.ufmt_display()?.map(|ufmt_display| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ufmt::uDisplay for #enum_ident #type_generics #where_clause {
+                #ufmt_display
+            }
+        }
+    });
+    let ufmt_debug = user_enum.ufmt_debug().map(|ufmt_debug| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ufmt::uDebug for #enum_ident #type_generics #where_clause {
+                #ufmt_debug
+            }
+        }
+    });
+
+    // and `Trace`, if the user asked for it
+    let trace = user_enum.trace().map(|trace| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::pisserror::Trace for #enum_ident #type_generics #where_clause {
+                #trace
+            }
+        }
+    });
+
+    // and a conversion into `std::io::Error`, if there were any `io_kind`s
+    let io_error_from = user_enum.io_error_from();
+
+    // raw codes come with some inherent items and a `TryFrom`
+    let (raw_codes, try_from_raw) = match user_enum.raw_codes()? {
+        Some((inherent, try_from)) => (Some(inherent), Some(try_from)),
+        None => (None, None),
+    };
+
+    // and an `extern "C"` function to look those codes up from C
+    let strerror = user_enum.strerror()?;
+
+    // plus any inherent methods the options asked for
+    let inherent_items = [
+        user_enum.constructors(),
+        user_enum.accessors(),
+        user_enum.to_problem_details(),
+        user_enum.localize(),
+        raw_codes,
+        user_enum.error_catalog(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    let inherent = (!inherent_items.is_empty()).then(|| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #enum_ident #type_generics #where_clause {
+                #(#inherent_items)*
+            }
+        }
+    });
+
+    // and a context selector for each variant, if the user asked for those
+    let context_selectors = user_enum.context_selectors()?;
+
     // put all those together!
     let impl_block = quote_spanned! {after_span=>
         #[automatically_derived]
@@ -52,7 +196,35 @@ pub(crate) fn derive_error(user_enum: &UserEnum) -> syn::Result<TokenStream2> {
             #fmt
         }
 
+        #debug
+
+        #serialize
+
+        #http_status
+
+        #process_exit
+
+        #severity
+
+        #trace
+
+        #defmt_format
+
+        #ufmt_display
+
+        #ufmt_debug
+
+        #inherent
+
+        #io_error_from
+
+        #try_from_raw
+
+        #strerror
+
         #froms
+
+        #context_selectors
     };
 
     Ok(impl_block)
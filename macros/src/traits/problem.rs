@@ -0,0 +1,120 @@
+//! # Problem
+//!
+//! Makes the `to_problem_details` method for the user's error type, if any
+//! of its variants use the `status`, `type_uri`, or `title` options.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::parser::UserEnum;
+
+impl UserEnum {
+    /// A `to_problem_details` method, which fills in a
+    /// `pisserror::ProblemDetails` using each variant's options.
+    ///
+    /// The `detail` member always comes from `Display`, and the `status`
+    /// from `HttpStatus`, so they match what the other traits say.
+    pub(crate) fn to_problem_details(&self) -> Option<TokenStream2> {
+        // `ProblemDetails` holds `String`s, so we need an allocator
+        if !cfg!(feature = "alloc") {
+            return None;
+        }
+
+        let has_options = self.attributes().status.is_some()
+            || self
+                .variants()
+                .iter()
+                .any(|v| v.options.has_problem_details());
+        if !has_options {
+            return None;
+        }
+
+        let match_arms = self.variants().iter().map(|v| {
+            let match_head = v.match_head(self.ident());
+            let type_uri = v.options.type_uri.as_ref().map(|t| quote!(.with_type(#t)));
+            let title = v.options.title.as_ref().map(|t| quote!(.with_title(#t)));
+
+            quote! { #match_head => problem #type_uri #title }
+        });
+
+        Some(quote! {
+            /// Describes this error as HTTP problem details (RFC 9457), using
+            /// the variant's `status`, `type_uri`, and `title` options. The
+            /// `detail` is this error's message.
+            #[must_use]
+            pub fn to_problem_details(&self) -> ::pisserror::ProblemDetails {
+                let problem = ::pisserror::ProblemDetails::for_error(self)
+                    .with_status(::pisserror::HttpStatus::status_code(self));
+
+                match *self {
+                    #(#match_arms),*
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn problem_details_use_variant_options() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("not found")]
+                #[error(status = 404, title = "Not Found")]
+                NotFound,
+                #[error("teapot: {_0}")]
+                #[error(type_uri = "https://example.com/teapot")]
+                Teapot(u8),
+                #[error("plain")]
+                Plain { reason: String },
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        if !cfg!(feature = "alloc") {
+            assert!(user_enum.to_problem_details().is_none());
+            return;
+        }
+
+        let expected: TokenStream2 = parse_quote! {
+            /// Describes this error as HTTP problem details (RFC 9457), using
+            /// the variant's `status`, `type_uri`, and `title` options. The
+            /// `detail` is this error's message.
+            #[must_use]
+            pub fn to_problem_details(&self) -> ::pisserror::ProblemDetails {
+                let problem = ::pisserror::ProblemDetails::for_error(self)
+                    .with_status(::pisserror::HttpStatus::status_code(self));
+
+                match *self {
+                    Piss::NotFound => problem.with_title("Not Found"),
+                    Piss::Teapot(..) => problem.with_type("https://example.com/teapot"),
+                    Piss::Plain { .. } => problem
+                }
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.to_problem_details().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn no_options_no_problem_details() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("not found")]
+                NotFound,
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        assert!(user_enum.to_problem_details().is_none());
+    }
+}
@@ -0,0 +1,376 @@
+//! # Raw
+//!
+//! Makes raw integer codes for the user's error type, if its variants have
+//! `errno` options. That includes the `as_raw` method, the `RAW_CODES` table,
+//! a `TryFrom` implementation for unit variants, and an `extern "C"` function
+//! for getting the messages from C.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{Ident, LitByteStr, LitInt, LitStr};
+
+use crate::parser::{
+    attr::{EnumAttributes, ErrorAttribute, RawCode},
+    field::WrappedFields,
+    variant::WrappedVariant,
+    UserEnum,
+};
+
+impl UserEnum {
+    /// The raw code items, if any `errno` options were given.
+    ///
+    /// Returns the inherent items (`RAW_CODES` and `as_raw`) alongside the
+    /// `TryFrom` implementation.
+    ///
+    /// # Errors
+    ///
+    /// Every variant needs a unique code that fits in the `repr` type.
+    pub(crate) fn raw_codes(&self) -> syn::Result<Option<(TokenStream2, TokenStream2)>> {
+        let has_errno = self.variants().iter().any(|v| v.options.errno.is_some());
+        if !has_errno {
+            return match self.attributes().repr {
+                Some(ref repr) => Err(Self::err_repr_without_errno(repr)),
+                None => Ok(None),
+            };
+        }
+
+        let repr = self
+            .attributes()
+            .repr
+            .clone()
+            .unwrap_or_else(|| Ident::new("i32", self.span()));
+        let codes = self.checked_codes(&repr)?;
+
+        let enum_ident = self.ident();
+        let (impl_generics, type_generics, where_clause) = self.generics().split_for_impl();
+
+        let table = self.variants().iter().zip(&codes).map(|(v, code)| {
+            let name = v.ident.to_string();
+            let message = Self::raw_message(v);
+            quote! { ::pisserror::RawCode::new(#code, #name, #message) }
+        });
+
+        let as_raw_arms = self.variants().iter().zip(&codes).map(|(v, code)| {
+            let match_head = v.match_head(enum_ident.clone());
+            quote! { #match_head => #code }
+        });
+
+        // only unit variants can be made from just a code
+        let try_from_arms = self
+            .variants()
+            .iter()
+            .zip(&codes)
+            .filter(|&(v, _)| matches!(v.fields, WrappedFields::Unit))
+            .map(|(v, code)| {
+                let variant_path = v.variant_path(enum_ident.clone());
+                quote! { #code => core::result::Result::Ok(#variant_path) }
+            });
+
+        let inherent = quote! {
+            /// Each variant's raw code, name, and message.
+            pub const RAW_CODES: &'static [::pisserror::RawCode<#repr>] = &[#(#table),*];
+
+            /// The raw code for this error, from the variant's `errno` option.
+            #[must_use]
+            pub const fn as_raw(&self) -> #repr {
+                match *self {
+                    #(#as_raw_arms),*
+                }
+            }
+        };
+
+        let try_from = quote! {
+            #[automatically_derived]
+            impl #impl_generics core::convert::TryFrom<#repr> for #enum_ident #type_generics #where_clause {
+                type Error = ::pisserror::UnknownCode<#repr>;
+
+                /// Finds the unit variant with the given raw code. This is
+                /// an error for unknown codes, and codes of variants with
+                /// fields.
+                fn try_from(code: #repr) -> core::result::Result<Self, Self::Error> {
+                    match code {
+                        #(#try_from_arms,)*
+                        other => core::result::Result::Err(::pisserror::UnknownCode(other)),
+                    }
+                }
+            }
+        };
+
+        Ok(Some((inherent, try_from)))
+    }
+
+    /// An `extern "C"` function that finds the message for a raw code, named
+    /// `pisserror_strerror` unless the `strerror` option renames it.
+    ///
+    /// It's `#[no_mangle]`, so `cbindgen` (and C) can find it. That also means
+    /// a crate with a few `errno` enums has to give each one its own name.
+    ///
+    /// # Errors
+    ///
+    /// The `strerror` option needs `errno` codes, and a free function can't
+    /// name the table of a generic enum.
+    pub(crate) fn strerror(&self) -> syn::Result<Option<TokenStream2>> {
+        let has_errno = self.variants().iter().any(|v| v.options.errno.is_some());
+        let is_generic = !self.generics().params.is_empty();
+
+        if let Some(ref name) = self.attributes().strerror {
+            if !has_errno {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "The `strerror` option is for `errno` codes, but no variant has one.",
+                ));
+            }
+            if is_generic {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "The `strerror` option doesn't support generic enums.",
+                ));
+            }
+        }
+        if !has_errno || is_generic {
+            return Ok(None);
+        }
+
+        let enum_ident = self.ident();
+        let vis = self.vis();
+        let name = self
+            .attributes()
+            .strerror
+            .clone()
+            .unwrap_or_else(|| Ident::new("pisserror_strerror", Span::call_site()));
+        let repr = self
+            .attributes()
+            .repr
+            .clone()
+            .unwrap_or_else(|| Ident::new("i32", self.span()));
+        let doc = format!(
+            "Describes one of `{enum_ident}`'s raw codes, as a `'static`, nul-terminated \
+            C string. Unknown codes get `\"unknown error\"`."
+        );
+
+        Ok(Some(quote! {
+            #[doc = #doc]
+            #[no_mangle]
+            #vis extern "C" fn #name(code: #repr) -> *const ::core::ffi::c_char {
+                ::pisserror::strerror(#enum_ident::RAW_CODES, code)
+            }
+        }))
+    }
+
+    /// Checks that every variant has a unique code that fits in `repr`, then
+    /// turns each one into a literal.
+    fn checked_codes(&self, repr: &Ident) -> syn::Result<Vec<TokenStream2>> {
+        // the `repr` was already checked while parsing
+        let (min, max) = EnumAttributes::repr_range(repr).unwrap_or((0, 0));
+        let mut seen: Vec<RawCode> = Vec::new();
+
+        self.variants()
+            .iter()
+            .map(|v| {
+                let Some(code) = v.options.errno else {
+                    return Err(syn::Error::new_spanned(
+                        &v.ident,
+                        "Since other variants have an `errno` option, this one needs one too.",
+                    ));
+                };
+
+                if code.value < min || code.value > max {
+                    return Err(syn::Error::new(
+                        code.span,
+                        format!("This code doesn't fit in the enum's `repr`, `{repr}`."),
+                    ));
+                }
+
+                if seen.iter().any(|other| other.value == code.value) {
+                    return Err(syn::Error::new(
+                        code.span,
+                        "Each variant's `errno` must be unique.",
+                    ));
+                }
+                seen.push(code);
+
+                Ok(Self::code_literal(code, repr))
+            })
+            .collect()
+    }
+
+    /// Makes a literal for the code, with the `repr` as its suffix.
+    ///
+    /// Lints look at the user's code when given its span, so this doesn't
+    /// use it. Otherwise, clippy would complain about the suffix.
+    fn code_literal(code: RawCode, repr: &Ident) -> TokenStream2 {
+        let lit = LitInt::new(
+            &format!("{}{repr}", code.value.unsigned_abs()),
+            Span::call_site(),
+        );
+
+        if code.value < 0 {
+            quote!(-#lit)
+        } else {
+            quote!(#lit)
+        }
+    }
+
+    /// The variant's message for the table, as a nul-terminated byte string.
+    ///
+    /// That's the variant's `#[error(...)]` string, without any fields filled
+    /// in. Variants without one (like `transparent` ones) use their name.
+    fn raw_message(v: &WrappedVariant) -> LitByteStr {
+        let literal = match v.error_attribute {
+            ErrorAttribute::Stringy(ref tokens) => tokens
+                .clone()
+                .into_iter()
+                .next()
+                .and_then(|first| syn::parse2::<LitStr>(first.into()).ok()),
+            ErrorAttribute::Transparent | ErrorAttribute::Many => None,
+        };
+
+        let (raw, span) = literal.map_or_else(
+            || (v.ident.to_string(), v.ident.span()),
+            |lit| (lit.value(), lit.span()),
+        );
+
+        // a nul byte would end the C string early
+        let message = raw.replace('\0', "");
+        LitByteStr::new(format!("{message}\0").as_bytes(), span)
+    }
+
+    fn err_repr_without_errno(repr: &Ident) -> syn::Error {
+        syn::Error::new_spanned(
+            repr,
+            "The `repr` option is for `errno` codes, but no variant has one.",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn raw_codes_use_repr() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(repr = i16)]
+            enum Piss {
+                #[error("invalid argument")]
+                #[error(errno = -22)]
+                Invalid,
+                #[error("no such file: {path}")]
+                #[error(errno = 2)]
+                NotFound { path: String },
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+        let (inherent, try_from) = user_enum.raw_codes().unwrap().unwrap();
+
+        let expected_inherent: TokenStream2 = parse_quote! {
+            /// Each variant's raw code, name, and message.
+            pub const RAW_CODES: &'static [::pisserror::RawCode<i16>] = &[
+                ::pisserror::RawCode::new(-22i16, "Invalid", b"invalid argument\0"),
+                ::pisserror::RawCode::new(2i16, "NotFound", b"no such file: {path}\0")
+            ];
+
+            /// The raw code for this error, from the variant's `errno` option.
+            #[must_use]
+            pub const fn as_raw(&self) -> i16 {
+                match *self {
+                    Piss::Invalid => -22i16,
+                    Piss::NotFound { .. } => 2i16
+                }
+            }
+        };
+        assert_eq!(expected_inherent.to_string(), inherent.to_string());
+
+        // only the unit variant is in `TryFrom`
+        let try_from_str = try_from.to_string();
+        assert!(try_from_str.contains(
+            &quote::quote!(- 22i16 => core::result::Result::Ok(Piss::Invalid)).to_string()
+        ));
+        assert!(!try_from_str.contains("NotFound"));
+    }
+
+    #[test]
+    fn strerror_is_extern_c() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(repr = u8, strerror = piss_strerror)]
+            pub(crate) enum Piss {
+                #[error("one")]
+                #[error(errno = 1)]
+                One,
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            #[doc = "Describes one of `Piss`'s raw codes, as a `'static`, nul-terminated C string. Unknown codes get `\"unknown error\"`."]
+            #[no_mangle]
+            pub(crate) extern "C" fn piss_strerror(code: u8) -> *const ::core::ffi::c_char {
+                ::pisserror::strerror(Piss::RAW_CODES, code)
+            }
+        };
+        assert_eq!(
+            expected.to_string(),
+            user_enum.strerror().unwrap().unwrap().to_string()
+        );
+
+        // it's only for `errno` codes, and can't name a generic enum's table
+        let no_codes: ItemEnum = parse_quote! {
+            #[error(strerror = piss_strerror)]
+            enum Piss {
+                #[error("one")]
+                One,
+            }
+        };
+        let generic: ItemEnum = parse_quote! {
+            #[error(strerror = piss_strerror)]
+            enum Piss<T> {
+                #[error("one")]
+                #[error(errno = 1)]
+                One(T),
+            }
+        };
+        for sauce in [no_codes, generic] {
+            let user_enum = UserEnum::new(sauce.into()).unwrap();
+            assert!(user_enum.strerror().is_err());
+        }
+    }
+
+    #[test]
+    fn raw_codes_are_checked() {
+        let missing: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("one")]
+                #[error(errno = 1)]
+                One,
+                #[error("two")]
+                Two,
+            }
+        };
+        let duplicate: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("one")]
+                #[error(errno = 1)]
+                One,
+                #[error("two")]
+                #[error(errno = 1)]
+                Two,
+            }
+        };
+        let too_big: ItemEnum = parse_quote! {
+            #[error(repr = u8)]
+            enum Piss {
+                #[error("one")]
+                #[error(errno = -1)]
+                One,
+            }
+        };
+
+        for sauce in [missing, duplicate, too_big] {
+            let user_enum = UserEnum::new(sauce.into()).unwrap();
+            assert!(user_enum.raw_codes().is_err());
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! # Serialize
+//!
+//! Implements serde's `Serialize` trait for the user's error type, if they
+//! asked for it with `#[error(serialize)]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Path;
+
+use crate::parser::{attr::ErrorAttribute, variant::WrappedVariant, UserEnum};
+
+impl UserEnum {
+    /// The `Serialize` trait's `serialize` method, if the user asked for one.
+    ///
+    /// Each variant becomes a struct with four fields: `kind`, `message`,
+    /// `fields`, and `source`. Sources are written using their `Display`
+    /// messages, so they don't need to implement `Serialize` themselves.
+    pub(crate) fn serialize(&self, error_path: &Path) -> Option<TokenStream2> {
+        if !self.attributes().serialize {
+            return None;
+        }
+
+        let enum_name = self.ident().to_string();
+
+        let match_arms = self.variants().iter().map(|v| {
+            let (match_head, fields) = self.serialized_fields(v);
+            let kind = v.ident.to_string();
+
+            quote! {
+                #match_head => {
+                    let mut state = ::pisserror::__private::serde::Serializer::serialize_struct(
+                        serializer, #enum_name, 4,
+                    )?;
+                    state.serialize_field("kind", #kind)?;
+                    state.serialize_field("message", &::pisserror::__private::Message(self))?;
+                    state.serialize_field("fields", &::pisserror::__private::Fields(#fields))?;
+                    state.serialize_field(
+                        "source",
+                        &::pisserror::__private::Source(#error_path::source(self)),
+                    )?;
+                    state.end()
+                }
+            }
+        });
+
+        Some(quote! {
+            fn serialize<__S>(&self, serializer: __S) -> core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: ::pisserror::__private::serde::Serializer,
+            {
+                use ::pisserror::__private::serde::ser::SerializeStruct as _;
+
+                match *self {
+                    #(#match_arms),*
+                }
+            }
+        })
+    }
+
+    /// Makes a match head that only binds the fields we'll serialize, along
+    /// with the list of those fields.
+    ///
+    /// `#[from]` and `#[source]` fields are left out, as they're already the
+    /// `source`.
+    fn serialized_fields(&self, v: &WrappedVariant) -> (TokenStream2, TokenStream2) {
+        let (match_head, bound) = v.partial_match_head(self.ident(), |field| {
+            !field.has_from_attribute() && !field.info().source && !field.info().skip_serializing
+        });
+
+        // each field is pushed in front of the ones after it, so go backwards
+        let list = bound.iter().rev().fold(quote!(()), |next, bound_field| {
+            let (ref binding, ref name) = *bound_field;
+
+            // `many` fields hold errors, which probably aren't `Serialize`
+            let value = match v.error_attribute {
+                ErrorAttribute::Many => quote!(&::pisserror::__private::Messages(#binding)),
+                ErrorAttribute::Stringy(_) | ErrorAttribute::Transparent => quote!(#binding),
+            };
+
+            quote! { ::pisserror::__private::Field::new(#name, #value, #next) }
+        });
+
+        (match_head, list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::{parser::UserEnum, util};
+
+    #[test]
+    #[allow(clippy::indexing_slicing)]
+    fn serialize_skips_from_and_skipped_fields() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(serialize)]
+            enum Piss {
+                #[error("failed to log in as {user}")]
+                Login {
+                    user: String,
+                    #[error(skip_serializing)]
+                    password: String,
+                },
+                #[error("disk error")]
+                Disk(#[from] std::io::Error),
+            }
+        };
+
+        if !cfg!(feature = "serde") {
+            assert!(UserEnum::new(sauce.into()).is_err());
+            return;
+        }
+
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+        let error_path = util::create_path(user_enum.span(), &["std", "error", "Error"]);
+
+        let (login_head, login_fields) = user_enum.serialized_fields(&user_enum.variants()[0]);
+        let (disk_head, disk_fields) = user_enum.serialized_fields(&user_enum.variants()[1]);
+
+        let expected: [TokenStream2; 4] = [
+            parse_quote!(Piss::Login { ref user, .. }),
+            parse_quote!(::pisserror::__private::Field::new("user", user, ())),
+            parse_quote!(Piss::Disk(_)),
+            parse_quote!(()),
+        ];
+        let got = [login_head, login_fields, disk_head, disk_fields];
+
+        for (e, g) in expected.iter().zip(got.iter()) {
+            assert_eq!(e.to_string(), g.to_string());
+        }
+        assert!(user_enum.serialize(&error_path).is_some());
+    }
+}
@@ -0,0 +1,152 @@
+//! # Status
+//!
+//! Implements `pisserror::HttpStatus` for the user's error type, if the enum
+//! or any of its variants has a problem details option (like `status`), or
+//! has a `transparent` variant that can ask its inner error.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::parser::{attr::ErrorAttribute, UserEnum};
+
+impl UserEnum {
+    /// The `HttpStatus` trait's `status_code` method, if any problem details
+    /// options were given (`to_problem_details` uses it), or any `transparent`
+    /// variants could delegate to their inner error.
+    ///
+    /// Variants without a `status` use the enum's, or `500` if it doesn't have
+    /// one either. However, `transparent` variants first check if their inner
+    /// error implements `HttpStatus`.
+    pub(crate) fn status_code(&self) -> Option<TokenStream2> {
+        let has_status = self.attributes().status.is_some()
+            || self
+                .variants()
+                .iter()
+                .any(|v| v.options.has_problem_details());
+        let can_delegate = self
+            .variants()
+            .iter()
+            .any(|v| matches!(v.error_attribute, ErrorAttribute::Transparent));
+        if !has_status && !can_delegate {
+            return None;
+        }
+
+        // 500 Internal Server Error
+        let default = self.attributes().status.unwrap_or(500);
+
+        let match_arms = self.variants().iter().map(|v| {
+            if let Some(status) = v.options.status {
+                let match_head = v.match_head(self.ident());
+                return quote! { #match_head => #status };
+            }
+
+            if matches!(v.error_attribute, ErrorAttribute::Transparent) {
+                if let Some((match_head, ident)) = v.match_head_with_from(self.ident(), true) {
+                    return quote! {
+                        #match_head => (&::pisserror::__private::StatusOf(#ident)).status_code_or(#default)
+                    };
+                }
+            }
+
+            let match_head = v.match_head(self.ident());
+            quote! { #match_head => #default }
+        });
+
+        Some(quote! {
+            fn status_code(&self) -> u16 {
+                #[allow(unused_imports)]
+                use ::pisserror::__private::{StatusFallback as _, StatusViaTrait as _};
+
+                match *self {
+                    #(#match_arms),*
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn status_code_uses_defaults_and_delegates() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(status = 503)]
+            enum Piss {
+                #[error("not found")]
+                #[error(status = 404)]
+                NotFound,
+                #[error(transparent)]
+                Inner(#[from] InnerError),
+                #[error("disk")]
+                Disk { #[from] io: std::io::Error },
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn status_code(&self) -> u16 {
+                #[allow(unused_imports)]
+                use ::pisserror::__private::{StatusFallback as _, StatusViaTrait as _};
+
+                match *self {
+                    Piss::NotFound => 404u16,
+                    Piss::Inner(ref e) => (&::pisserror::__private::StatusOf(e)).status_code_or(503u16),
+                    Piss::Disk { .. } => 503u16
+                }
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.status_code().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn transparent_variants_delegate() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error(transparent)]
+                Inner(#[from] InnerError),
+                #[error("not found")]
+                NotFound,
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn status_code(&self) -> u16 {
+                #[allow(unused_imports)]
+                use ::pisserror::__private::{StatusFallback as _, StatusViaTrait as _};
+
+                match *self {
+                    Piss::Inner(ref e) => (&::pisserror::__private::StatusOf(e)).status_code_or(500u16),
+                    Piss::NotFound => 500u16
+                }
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.status_code().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn no_status_no_impl() {
+        let sauce: ItemEnum = parse_quote! {
+            enum Piss {
+                #[error("not found")]
+                NotFound,
+            }
+        };
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        assert!(user_enum.status_code().is_none());
+    }
+}
@@ -0,0 +1,129 @@
+//! # Trace
+//!
+//! Implements pisserror's `Trace` trait for the user's error type, if they
+//! asked for it with `#[error(trace)]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::parser::UserEnum;
+
+impl UserEnum {
+    /// The `Trace` trait's `trace` method, if the user asked for it.
+    ///
+    /// `tracing` needs its level to be known where the event is made, so the
+    /// fields are handed to a little macro that makes one event per level.
+    pub(crate) fn trace(&self) -> Option<TokenStream2> {
+        if !self.attributes().trace {
+            return None;
+        }
+
+        // `as_raw` only exists when the variants have codes
+        let has_errno = self.variants().iter().any(|v| v.options.errno.is_some());
+        let code = has_errno.then(|| quote!(error.code = self.as_raw(),));
+
+        let match_arms = self.variants().iter().map(|v| {
+            let (match_head, bound) =
+                v.partial_match_head(self.ident(), |field| !field.info().skip_serializing);
+            let kind = v.ident.to_string();
+            let fields = bound.iter().map(|bound_field| {
+                let binding = &bound_field.0;
+                quote!(#binding = ?#binding)
+            });
+
+            quote! {
+                #match_head => __pisserror_event!(
+                    error.kind = #kind,
+                    #code
+                    error.message = %self,
+                    #(#fields),*
+                )
+            }
+        });
+
+        Some(quote! {
+            fn trace(&self, level: ::pisserror::__private::tracing::Level) {
+                use ::pisserror::__private::tracing::{event, Level};
+
+                macro_rules! __pisserror_event {
+                    ($($fields:tt)*) => {
+                        if level == Level::ERROR {
+                            event!(Level::ERROR, $($fields)*)
+                        } else if level == Level::WARN {
+                            event!(Level::WARN, $($fields)*)
+                        } else if level == Level::INFO {
+                            event!(Level::INFO, $($fields)*)
+                        } else if level == Level::DEBUG {
+                            event!(Level::DEBUG, $($fields)*)
+                        } else {
+                            event!(Level::TRACE, $($fields)*)
+                        }
+                    };
+                }
+
+                match *self {
+                    #(#match_arms),*
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn trace_records_unskipped_fields() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(trace)]
+            enum Piss {
+                #[error("failed to log in as {user}")]
+                Login {
+                    user: String,
+                    #[error(skip_serializing)]
+                    password: String,
+                },
+                #[error("disk error")]
+                Disk(#[from] std::io::Error),
+                #[error("ran out of toilet paper")]
+                NoPaper,
+            }
+        };
+        if !cfg!(feature = "tracing") {
+            assert!(UserEnum::new(sauce.into()).is_err());
+            return;
+        }
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+        let trace_str = user_enum.trace().unwrap().to_string();
+
+        let expected_arms = [
+            quote::quote! {
+                Piss::Login { ref user, .. } => __pisserror_event!(
+                    error.kind = "Login",
+                    error.message = %self,
+                    user = ?user
+                )
+            },
+            quote::quote! {
+                Piss::Disk(ref _0) => __pisserror_event!(
+                    error.kind = "Disk",
+                    error.message = %self,
+                    _0 = ?_0
+                )
+            },
+            quote::quote! {
+                Piss::NoPaper => __pisserror_event!(
+                    error.kind = "NoPaper",
+                    error.message = %self,
+                )
+            },
+        ];
+
+        for arm in expected_arms {
+            assert!(trace_str.contains(&arm.to_string()), "missing arm: {arm}");
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! # Ufmt
+//!
+//! Implements `ufmt::uDisplay` and `ufmt::uDebug` for the user's error type,
+//! if they asked for it with `#[error(ufmt)]`. Messages are turned into
+//! direct calls to the formatter, so no `core::fmt` machinery is involved.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{LitBool, LitByte, LitInt, LitStr};
+
+use crate::parser::{
+    attr::ErrorAttribute,
+    format::{Message, Piece},
+    UserEnum,
+};
+
+impl UserEnum {
+    /// The `ufmt::uDisplay` trait's `fmt` method, if the user asked for one.
+    ///
+    /// Like `defmt`, this is opt-in, and the user's crate needs to depend on
+    /// `ufmt` itself.
+    ///
+    /// # Errors
+    ///
+    /// Each message can only use format specs that `ufmt` supports.
+    pub(crate) fn ufmt_display(&self) -> syn::Result<Option<TokenStream2>> {
+        if !self.attributes().ufmt {
+            return Ok(None);
+        }
+
+        let mut match_arms = self
+            .variants()
+            .iter()
+            .map(|v| {
+                let match_head = v.filled_match_head(self.ident(), true);
+
+                let body = match v.error_attribute {
+                    ErrorAttribute::Stringy(ref tokens) => {
+                        let writes = Self::ufmt_message(&Message::parse(tokens)?)?;
+                        quote! {
+                            #(#writes)*
+                            Ok(())
+                        }
+                    }
+
+                    ErrorAttribute::Transparent => {
+                        let from_field = v.first_field_binding();
+                        quote! { ufmt::uDisplay::fmt(#from_field, f) }
+                    }
+
+                    ErrorAttribute::Many => {
+                        let many_field = v.first_field_binding();
+
+                        quote! {
+                            for (i, e) in core::iter::IntoIterator::into_iter(#many_field).enumerate() {
+                                if i != 0 {
+                                    f.write_str("\n")?;
+                                }
+                                ufmt::uDisplay::fmt(&i.saturating_add(1), f)?;
+                                f.write_str(". ")?;
+                                ufmt::uDisplay::fmt(e, f)?;
+                            }
+                            Ok(())
+                        }
+                    }
+                };
+
+                Ok(quote! { #match_head => { #body } })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        // like `Display`, an enum without variants still needs an arm
+        if match_arms.is_empty() {
+            match_arms.push(quote! { _ => Ok(()) });
+        }
+
+        Ok(Some(quote! {
+            fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> core::result::Result<(), W::Error>
+            where
+                W: ufmt::uWrite + ?Sized,
+            {
+                match *self {
+                    #(#match_arms),*
+                }
+            }
+        }))
+    }
+
+    /// The `ufmt::uDebug` trait's `fmt` method, if the user asked for one.
+    ///
+    /// Fields don't have to be `uDebug`, since this just writes the message,
+    /// like `#[error(debug = display)]` does for `Debug`.
+    pub(crate) fn ufmt_debug(&self) -> Option<TokenStream2> {
+        self.attributes().ufmt.then(|| {
+            quote! {
+                fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> core::result::Result<(), W::Error>
+                where
+                    W: ufmt::uWrite + ?Sized,
+                {
+                    ufmt::uDisplay::fmt(self, f)
+                }
+            }
+        })
+    }
+
+    /// Turns a message into a list of writes to the formatter.
+    fn ufmt_message(message: &Message) -> syn::Result<Vec<TokenStream2>> {
+        message
+            .pieces
+            .iter()
+            .map(|piece| match *piece {
+                Piece::Text(ref text) => {
+                    let text_lit = LitStr::new(text, message.lit.span());
+                    Ok(quote! { f.write_str(#text_lit)?; })
+                }
+                Piece::Argument {
+                    ref value,
+                    ref spec,
+                } => match spec.as_str() {
+                    "" => Ok(quote! { ufmt::uDisplay::fmt(&(#value), f)?; }),
+                    "?" => Ok(quote! { ufmt::uDebug::fmt(&(#value), f)?; }),
+                    "#?" => Ok(quote! { f.pretty(|f| ufmt::uDebug::fmt(&(#value), f))?; }),
+                    _ => {
+                        let options = Self::ufmt_hex_options(spec)
+                            .ok_or_else(|| Self::err_unsupported_ufmt_spec(&message.lit, spec))?;
+                        // `uDisplayHex` isn't implemented for references,
+                        // so let method calls deref the bindings for us
+                        Ok(quote! {{
+                            use ufmt::uDisplayHex as _;
+                            (#value).fmt_hex(f, #options)?;
+                        }})
+                    }
+                },
+            })
+            .collect()
+    }
+
+    /// Reads a hex spec, like `#010x`, into `ufmt::HexOptions`. That's the
+    /// only kind of spec `ufmt` has besides `?` and `#?`.
+    fn ufmt_hex_options(spec: &str) -> Option<TokenStream2> {
+        let (prefix, after_prefix) = spec
+            .strip_prefix('#')
+            .map_or((false, spec), |rest| (true, rest));
+        let (pad_char, after_pad_char) = after_prefix
+            .strip_prefix('0')
+            .map_or((b' ', after_prefix), |rest| (b'0', rest));
+
+        let digits = after_pad_char.len()
+            - after_pad_char
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        let (pad_digits, kind) = after_pad_char.split_at(digits);
+        let pad_length = if pad_digits.is_empty() {
+            0
+        } else {
+            pad_digits.parse::<usize>().ok()?
+        };
+
+        let is_upper = match kind {
+            "x" => false,
+            "X" => true,
+            _ => return None,
+        };
+
+        // these don't use the user's span, so clippy doesn't look at them
+        let upper_case = LitBool::new(is_upper, Span::call_site());
+        let pad_char_lit = LitByte::new(pad_char, Span::call_site());
+        let pad_length_lit = LitInt::new(&pad_length.to_string(), Span::call_site());
+        let ox_prefix = LitBool::new(prefix, Span::call_site());
+
+        Some(quote! {
+            ufmt::HexOptions {
+                upper_case: #upper_case,
+                pad_char: #pad_char_lit,
+                pad_length: #pad_length_lit,
+                ox_prefix: #ox_prefix,
+            }
+        })
+    }
+
+    fn err_unsupported_ufmt_spec(lit: &LitStr, spec: &str) -> syn::Error {
+        syn::Error::new_spanned(
+            lit,
+            format!(
+                "`ufmt` doesn't support the format spec `{{:{spec}}}`. It only supports \
+                `{{}}`, `{{:?}}`, `{{:#?}}`, and hex specs like `{{:#010x}}`."
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::{parse_quote, ItemEnum};
+
+    use crate::parser::UserEnum;
+
+    #[test]
+    fn ufmt_writes_each_piece() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(ufmt)]
+            enum Piss {
+                #[error("register {_0:#04x} is {_1:?}")]
+                Register(u8, State),
+                #[error(transparent)]
+                Other(#[from] OtherError),
+            }
+        };
+
+        if !cfg!(feature = "ufmt") {
+            assert!(UserEnum::new(sauce.into()).is_err());
+            return;
+        }
+        let user_enum = UserEnum::new(sauce.into()).unwrap();
+
+        let expected: TokenStream2 = parse_quote! {
+            fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> core::result::Result<(), W::Error>
+            where
+                W: ufmt::uWrite + ?Sized,
+            {
+                match *self {
+                    Piss::Register(ref _0, ref _1) => {
+                        f.write_str("register ")?;
+                        {
+                            use ufmt::uDisplayHex as _;
+                            (_0).fmt_hex(f, ufmt::HexOptions {
+                                upper_case: false,
+                                pad_char: b'0',
+                                pad_length: 4,
+                                ox_prefix: true,
+                            })?;
+                        }
+                        f.write_str(" is ")?;
+                        ufmt::uDebug::fmt(&(_1), f)?;
+                        Ok(())
+                    },
+                    Piss::Other(ref _0) => { ufmt::uDisplay::fmt(_0, f) }
+                }
+            }
+        };
+
+        assert_eq!(
+            expected.to_string(),
+            user_enum.ufmt_display().unwrap().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn ufmt_rejects_unsupported_specs() {
+        let sauce: ItemEnum = parse_quote! {
+            #[error(ufmt)]
+            enum Piss {
+                #[error("{_0:.2}")]
+                Precise(f32),
+            }
+        };
+
+        if cfg!(feature = "ufmt") {
+            let user_enum = UserEnum::new(sauce.into()).unwrap();
+            assert!(user_enum.ufmt_display().is_err());
+        }
+    }
+}
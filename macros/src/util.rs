@@ -3,7 +3,7 @@
 //! Some utilities to help out with writing these macros.
 
 use proc_macro2::Span as Span2;
-use syn::{punctuated::Punctuated, Ident, Path, PathSegment};
+use syn::{punctuated::Punctuated, GenericArgument, Ident, Path, PathArguments, PathSegment, Type};
 
 pub(crate) fn create_path(span: Span2, ident_strs: &[&str]) -> Path {
     Path {
@@ -20,3 +20,125 @@ pub(crate) fn create_path(span: Span2, ident_strs: &[&str]) -> Path {
         },
     }
 }
+
+/// Checks if the given type looks like `std::io::Error`. That's anything
+/// ending in `io::Error`.
+pub(crate) fn is_io_error(ty: &Type) -> bool {
+    let Type::Path(ref type_path) = *ty else {
+        return false;
+    };
+
+    let mut segments = type_path.path.segments.iter().rev();
+    matches!(
+        (segments.next(), segments.next()),
+        (Some(last), Some(second_last)) if last.ident == "Error" && second_last.ident == "io"
+    )
+}
+
+/// The type inside a `Box`, `Arc`, or `Rc`, if the given type is one of
+/// those. For example, `io::Error` for `Arc<io::Error>`.
+///
+/// Macros can't see what a name resolves to, so this goes by the last
+/// segment alone. Any type named `Box`, `Arc`, or `Rc` (with a type as its
+/// first generic argument) counts, even if it's the user's own.
+pub(crate) fn pointee(ty: &Type) -> Option<&Type> {
+    let Type::Path(ref type_path) = *ty else {
+        return None;
+    };
+
+    let last = type_path.path.segments.last()?;
+    if !matches!(last.ident.to_string().as_str(), "Box" | "Arc" | "Rc") {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(ref args) = last.arguments else {
+        return None;
+    };
+
+    match *args.args.first()? {
+        GenericArgument::Type(ref inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// The type inside an `Option`, if the given type is one. For example,
+/// `io::Error` for `Option<io::Error>`.
+pub(crate) fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(ref type_path) = *ty else {
+        return None;
+    };
+
+    let last = type_path.path.segments.last()?;
+    if last.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(ref args) = last.arguments else {
+        return None;
+    };
+
+    match *args.args.first()? {
+        GenericArgument::Type(ref inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// Writes the given type like a person would, like `Vec<String>` rather than
+/// the `Vec < String >` that tokens turn into.
+pub(crate) fn type_name(ty: &Type) -> String {
+    let spaced = quote::quote!(#ty).to_string();
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '\'';
+
+    let mut name = String::with_capacity(spaced.len());
+    let mut chars = spaced.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != ' ' {
+            name.push(c);
+            continue;
+        }
+
+        // only keep spaces between words, around `+`, and after separators
+        let prev = name.chars().last().unwrap_or(' ');
+        let next = chars.peek().copied().unwrap_or(' ');
+        if (is_word(prev) && is_word(next)) || matches!(prev, ',' | ';' | '+') || next == '+' {
+            name.push(' ');
+        }
+    }
+
+    name
+}
+
+/// Turns a variant's name into a function name, like `MalformedHeader` into
+/// `malformed_header`. Names that turn into keywords are made raw.
+pub(crate) fn snake_case(ident: &Ident) -> Ident {
+    let name = ident.to_string();
+    let chars = name.chars().collect::<Vec<_>>();
+
+    let mut snake = String::with_capacity(name.len().saturating_add(4));
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).and_then(|p| chars.get(p)).copied();
+            let next = chars.get(i.saturating_add(1)).copied();
+
+            // split before a new word, like `IoError` or the `E` in `IOError`
+            let after_lower = prev.map_or(false, |p| p.is_lowercase() || p.is_ascii_digit());
+            let ends_acronym =
+                prev.map_or(false, char::is_uppercase) && next.map_or(false, char::is_lowercase);
+            if (after_lower || ends_acronym) && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    match syn::parse_str::<Ident>(&snake) {
+        Ok(_) => Ident::new(&snake, ident.span()),
+        // these can't be raw, so they get an underscore instead
+        Err(_) if matches!(snake.as_str(), "self" | "super" | "crate") => {
+            Ident::new(&format!("{snake}_"), ident.span())
+        }
+        Err(_) => Ident::new_raw(&snake, ident.span()),
+    }
+}
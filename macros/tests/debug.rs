@@ -0,0 +1,64 @@
+//! Checks the `Debug` implementations made by `#[error(debug = ...)]`.
+
+#[cfg(test)]
+#[allow(clippy::use_debug)]
+mod tests {
+    use pisserror_macros::Error;
+    use std::error::Error;
+
+    #[derive(Error)]
+    #[error(debug = display)]
+    enum DisplayDebug {
+        #[error("key `{_0}` not found")]
+        KeyNotFound(String),
+        #[error("disk error")]
+        Disk {
+            #[from]
+            inner: std::io::Error,
+        },
+        #[error("nothing")]
+        Unit,
+    }
+
+    #[derive(Error)]
+    #[error(debug = report)]
+    enum ReportDebug {
+        #[error("failed to load the config")]
+        Load(#[from] DisplayDebug),
+        #[error("no config")]
+        Missing,
+    }
+
+    fn disk_error() -> DisplayDebug {
+        std::io::Error::other("smoke detected").into()
+    }
+
+    #[test]
+    fn debug_display() {
+        let err = DisplayDebug::KeyNotFound(String::from("piss"));
+
+        assert_eq!(format!("{err:?}"), "key `piss` not found");
+        assert_eq!(format!("{err:#?}"), "KeyNotFound(\n    \"piss\",\n)");
+        assert_eq!(format!("{:#?}", DisplayDebug::Unit), "Unit");
+        assert!(format!("{:#?}", disk_error()).starts_with("Disk {\n    inner: "));
+    }
+
+    #[test]
+    fn debug_report() {
+        let one_cause = ReportDebug::from(DisplayDebug::Unit);
+        assert_eq!(
+            format!("{one_cause:?}"),
+            "failed to load the config\n\nCaused by:\n      nothing"
+        );
+
+        let two_causes = ReportDebug::from(disk_error());
+        assert_eq!(
+            format!("{two_causes:?}"),
+            "failed to load the config\n\nCaused by:\n      0: disk error\n      1: smoke detected"
+        );
+
+        assert_eq!(format!("{:?}", ReportDebug::Missing), "no config");
+        assert_eq!(format!("{:#?}", ReportDebug::Missing), "Missing");
+        assert!(two_causes.source().is_some());
+    }
+}
@@ -28,6 +28,23 @@ mod tests {
         // TODO: try formatting the error
     }
 
+    #[cfg(feature = "alloc")]
+    #[derive(Debug, Error)]
+    enum Boxed {
+        #[error("something went wrong")]
+        Dynamic(#[from] Box<dyn Error + Send + Sync>),
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn boxed_trait_object_source() {
+        let ioerr = std::io::Error::from_raw_os_error(22);
+        let boxed = Boxed::from(Box::<dyn Error + Send + Sync>::from(ioerr));
+
+        // we should see the inner error, not the box
+        assert!(boxed.source().unwrap().is::<std::io::Error>());
+    }
+
     #[derive(Debug, Error)]
     enum Transparent {
         #[error(transparent)]
@@ -0,0 +1,44 @@
+//! Checks that `#[error(many)]` variants print each of their errors.
+
+#[cfg(test)]
+mod tests {
+    use core::num::ParseIntError;
+    use pisserror_macros::Error;
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    enum ConfigError {
+        #[error(many)]
+        Invalid(Vec<ParseIntError>),
+        #[error(many)]
+        InvalidNamed { problems: Vec<ParseIntError> },
+        #[error(many)]
+        FromMany(#[from] Vec<std::io::Error>),
+    }
+
+    #[test]
+    fn many_variants_number_each_error() {
+        let problems = ["eighty", ""]
+            .iter()
+            .filter_map(|p| p.parse::<u16>().err())
+            .collect::<Vec<_>>();
+
+        let expected =
+            "1. invalid digit found in string\n2. cannot parse integer from empty string";
+
+        let tuple = ConfigError::Invalid(problems.clone());
+        assert_eq!(tuple.to_string(), expected);
+        assert!(tuple.source().is_none());
+
+        let named = ConfigError::InvalidNamed { problems };
+        assert_eq!(named.to_string(), expected);
+    }
+
+    #[test]
+    fn many_from_variant() {
+        let err = ConfigError::from(vec![std::io::Error::other("disk on fire")]);
+
+        assert_eq!(err.to_string(), "1. disk on fire");
+        assert!(err.source().is_none());
+    }
+}
@@ -37,3 +37,169 @@ enum MyError<'a> {
 ```
 */
 pub fn from_attr_cant_use_nonstatic_lifetime() {}
+
+/**
+A `many` variant prints each item in its one field, so it can't have more (or
+less) than one.
+
+```compile_fail
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Debug, Error)]
+enum ValidationError {
+    #[error(many)]
+    TooManyFields(Vec<std::io::Error>, Vec<std::io::Error>),
+}
+```
+*/
+pub fn many_attr_needs_one_field() {}
+
+/**
+The `debug` option only knows about `display` and `report`.
+
+```compile_fail
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Error)]
+#[error(debug = yaml)]
+enum UnknownDebugStyle {
+    #[error("hi")]
+    Variant,
+}
+```
+*/
+pub fn debug_option_must_be_known() {}
+
+/**
+Fields only understand `#[error(skip_serializing)]`.
+
+```compile_fail
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Debug, Error)]
+enum UnknownFieldOption {
+    #[error("hi {name}")]
+    Variant {
+        #[error(redact)]
+        name: String,
+    },
+}
+```
+*/
+pub fn field_option_must_be_known() {}
+
+/**
+A variant's `status` must be a real HTTP status code.
+
+```compile_fail
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Debug, Error)]
+enum BadStatus {
+    #[error("hi")]
+    #[error(status = 1000)]
+    Variant,
+}
+```
+*/
+pub fn status_must_be_http_status() {}
+
+/**
+Variants only understand the options they know about.
+
+```compile_fail
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Debug, Error)]
+enum UnknownVariantOption {
+    #[error("hi")]
+    #[error(colour = "red")]
+    Variant,
+}
+```
+*/
+pub fn variant_option_must_be_known() {}
+
+/**
+With `#[error(defmt)]`, messages can't use format specs that `defmt` has no
+way to express, like padding.
+
+```compile_fail
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Debug, Error)]
+#[error(defmt)]
+enum Padded {
+    #[error("the value is {_0:>8}")]
+    Value(u32),
+}
+```
+*/
+#[cfg(feature = "defmt")]
+pub fn defmt_rejects_unsupported_specs() {}
+
+/**
+With `#[error(ufmt)]`, messages can only use the format specs that `ufmt`
+supports.
+
+```compile_fail
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Debug, Error)]
+#[error(ufmt)]
+enum Precise {
+    #[error("the value is {_0:.2}")]
+    Value(f32),
+}
+```
+*/
+#[cfg(feature = "ufmt")]
+pub fn ufmt_rejects_unsupported_specs() {}
+
+/**
+In `error_set!`, a set can only be made from sets defined before it.
+
+```compile_fail
+use pisserror::error_set;
+use std::error::Error;
+
+error_set! {
+    #[derive(Debug)]
+    LoadError = ParseError || {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    };
+
+    #[derive(Debug)]
+    ParseError = {
+        #[error("unexpected end of input")]
+        Eof,
+    };
+}
+```
+*/
+pub fn error_sets_must_be_defined_first() {}
+
+/**
+`#[from]` fields can't be `Option`s, since there's no good way to convert
+into a source that might not be there. `#[source]` should be used instead.
+
+```compile_fail
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Debug, Error)]
+enum RetryError {
+    #[error("gave up")]
+    GaveUp(#[from] Option<std::io::Error>),
+}
+```
+*/
+pub fn from_fields_cant_be_options() {}
@@ -0,0 +1,313 @@
+//! # Any
+//!
+//! A type-erased error for application code.
+
+use alloc::boxed::Box;
+use core::{
+    fmt::{self, Debug, Display},
+    ops::Deref,
+};
+
+use crate::{
+    attach::{Attached, Attachment},
+    report::{self, Chain, Reportable},
+    StdError,
+};
+
+/// A boxed, type-erased error.
+///
+/// Libraries should generally derive their own error enums, but binaries
+/// often just want to bubble *something* up to `main`. `AnyError` can hold
+/// any error, so `?` works on everything:
+///
+/// ```
+/// use pisserror::{AnyError, WrapErr as _};
+///
+/// fn parse_port(s: &str) -> Result<u16, AnyError> {
+///     let port = s.parse::<u16>().wrap_err("the port wasn't a number")?;
+///     Ok(port)
+/// }
+///
+/// let err = parse_port("eighty").unwrap_err();
+/// assert_eq!(err.to_string(), "the port wasn't a number");
+/// assert_eq!(
+///     format!("{err:#}"),
+///     "the port wasn't a number: invalid digit found in string"
+/// );
+/// ```
+///
+/// When printed with `Debug`, an `AnyError` renders like a pretty
+/// [`Report`](crate::Report), so returning one from `main` shows the whole
+/// chain of sources.
+pub struct AnyError {
+    inner: Box<dyn StdError + Send + Sync + 'static>,
+}
+
+impl AnyError {
+    /// Wraps the given error.
+    #[inline]
+    pub fn new<E>(error: E) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        Self {
+            inner: Box::new(error),
+        }
+    }
+
+    /// Creates an error from a plain message.
+    #[inline]
+    pub fn msg<M>(message: M) -> Self
+    where
+        M: Display + Debug + Send + Sync + 'static,
+    {
+        Self::new(MessageError(message))
+    }
+
+    /// Wraps this error with some more context. The current error becomes
+    /// the context's `source`.
+    #[inline]
+    #[must_use]
+    pub fn context<C>(self, context: C) -> Self
+    where
+        C: Display + Debug + Send + Sync + 'static,
+    {
+        Self::new(ContextError {
+            context,
+            source: self.inner,
+        })
+    }
+
+    /// Attaches a value to this error. See
+    /// [`Attach::attach`](crate::Attach::attach).
+    #[inline]
+    #[must_use]
+    pub fn attach<A>(self, attachment: A) -> Self
+    where
+        A: Send + Sync + 'static,
+    {
+        let mut attached = Attached::from_boxed(self.inner);
+        attached.push(Attachment::new(attachment));
+        Self::new(attached)
+    }
+
+    /// Attaches a printable value to this error. See
+    /// [`Attach::attach_printable`](crate::Attach::attach_printable).
+    #[inline]
+    #[must_use]
+    pub fn attach_printable<A>(self, attachment: A) -> Self
+    where
+        A: Display + Send + Sync + 'static,
+    {
+        let mut attached = Attached::from_boxed(self.inner);
+        attached.push(Attachment::new_printable(attachment));
+        Self::new(attached)
+    }
+
+    /// Finds the most recent attachment of the given type anywhere in this
+    /// error's chain.
+    #[inline]
+    pub fn request_ref<A: 'static>(&self) -> Option<&A> {
+        report::request_ref(self.chain())
+    }
+
+    /// Checks if the outermost error is of the given type.
+    #[inline]
+    pub fn is<E: StdError + 'static>(&self) -> bool {
+        self.inner.is::<E>()
+    }
+
+    /// Grabs a reference to the outermost error, if it's of the given type.
+    #[inline]
+    pub fn downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+        self.inner.downcast_ref::<E>()
+    }
+
+    /// Grabs a mutable reference to the outermost error, if it's of the
+    /// given type.
+    #[inline]
+    pub fn downcast_mut<E: StdError + 'static>(&mut self) -> Option<&mut E> {
+        self.inner.downcast_mut::<E>()
+    }
+
+    /// Attempts to take the outermost error out as the given type.
+    ///
+    /// # Errors
+    ///
+    /// If the error isn't of the given type, you get the `AnyError` back.
+    #[inline]
+    pub fn downcast<E: StdError + 'static>(self) -> Result<E, Self> {
+        match self.inner.downcast::<E>() {
+            Ok(error) => Ok(*error),
+            Err(inner) => Err(Self { inner }),
+        }
+    }
+
+    /// An iterator over this error and each of its sources.
+    #[inline]
+    pub fn chain(&self) -> Chain<'_> {
+        Chain::new(self.as_error())
+    }
+
+    /// The innermost error in the chain.
+    #[inline]
+    pub fn root_cause(&self) -> &(dyn StdError + 'static) {
+        self.chain().last().unwrap_or_else(|| self.as_error())
+    }
+
+    /// Unwraps the underlying boxed error.
+    #[inline]
+    pub fn into_boxed(self) -> Box<dyn StdError + Send + Sync + 'static> {
+        self.inner
+    }
+}
+
+impl<E> From<E> for AnyError
+where
+    E: StdError + Send + Sync + 'static,
+{
+    #[inline]
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl From<AnyError> for Box<dyn StdError + Send + Sync + 'static> {
+    #[inline]
+    fn from(error: AnyError) -> Self {
+        error.inner
+    }
+}
+
+impl Deref for AnyError {
+    type Target = dyn StdError + Send + Sync + 'static;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &*self.inner
+    }
+}
+
+impl Reportable for AnyError {
+    #[inline]
+    fn as_error(&self) -> &(dyn StdError + 'static) {
+        &*self.inner
+    }
+}
+
+impl Display for AnyError {
+    /// Prints the outermost error. Use the alternate flag (`{:#}`) to print
+    /// the whole chain on one line.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            report::render(self.as_error(), f, false)
+        } else {
+            Display::fmt(&self.inner, f)
+        }
+    }
+}
+
+impl Debug for AnyError {
+    /// Prints a pretty report. Use the alternate flag (`{:#?}`) to see the
+    /// underlying structure instead.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("AnyError")
+                .field("inner", &self.inner)
+                .finish()
+        } else {
+            report::render(self.as_error(), f, true)
+        }
+    }
+}
+
+/// Adds context to the error in a `Result`, turning it into an [`AnyError`].
+pub trait WrapErr<T> {
+    /// Wraps the error with the given context.
+    ///
+    /// # Errors
+    ///
+    /// Passes along the error, if there was one.
+    fn wrap_err<C>(self, context: C) -> Result<T, AnyError>
+    where
+        C: Display + Debug + Send + Sync + 'static;
+
+    /// Wraps the error with context that's only created when there is an
+    /// error.
+    ///
+    /// # Errors
+    ///
+    /// Passes along the error, if there was one.
+    fn wrap_err_with<C, F>(self, context: F) -> Result<T, AnyError>
+    where
+        C: Display + Debug + Send + Sync + 'static,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> WrapErr<T> for Result<T, E>
+where
+    E: Into<AnyError>,
+{
+    #[inline]
+    fn wrap_err<C>(self, context: C) -> Result<T, AnyError>
+    where
+        C: Display + Debug + Send + Sync + 'static,
+    {
+        self.map_err(|e| e.into().context(context))
+    }
+
+    #[inline]
+    fn wrap_err_with<C, F>(self, context: F) -> Result<T, AnyError>
+    where
+        C: Display + Debug + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|e| e.into().context(context()))
+    }
+}
+
+/// An error that's just a message.
+struct MessageError<M>(M);
+
+impl<M: Debug> Debug for MessageError<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<M: Display> Display for MessageError<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<M: Display + Debug> StdError for MessageError<M> {}
+
+/// Some context sitting on top of another error.
+struct ContextError<C> {
+    context: C,
+    source: Box<dyn StdError + Send + Sync + 'static>,
+}
+
+impl<C: Debug> Debug for ContextError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextError")
+            .field("context", &self.context)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl<C: Display> Display for ContextError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.context, f)
+    }
+}
+
+impl<C: Display + Debug> StdError for ContextError<C> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&*self.source)
+    }
+}
@@ -0,0 +1,215 @@
+//! # Attach
+//!
+//! Typed attachments that ride along with an error as it propagates.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    any::Any,
+    fmt::{self, Debug, Display},
+};
+
+use crate::StdError;
+
+/// An error carrying some extra attachments.
+///
+/// Attachments can be any `'static` type, like a request ID or the path that
+/// failed to open. "Printable" attachments also show up when the error is
+/// rendered with a [`Report`](crate::Report).
+///
+/// Otherwise, an `Attached` error acts just like the error inside it: it has
+/// the same message and the same `source`. This means it can go anywhere an
+/// error can, including into a `#[from]` variant on your own enum.
+///
+/// ```
+/// use pisserror::{Attach as _, Report};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct RequestId(u32);
+///
+/// let io = std::io::Error::new(std::io::ErrorKind::Other, "connection reset");
+/// let err = io
+///     .attach(RequestId(42))
+///     .attach_printable("while syncing shard 3");
+///
+/// let report = Report::new(err);
+/// assert_eq!(report.request_ref::<RequestId>(), Some(&RequestId(42)));
+/// assert_eq!(report.to_string(), "connection reset (while syncing shard 3)");
+/// ```
+pub struct Attached {
+    error: Box<dyn StdError + Send + Sync + 'static>,
+    attachments: Vec<Attachment>,
+}
+
+impl Attached {
+    /// Wraps the given error. If it's already an `Attached`, it's returned
+    /// as-is.
+    #[inline]
+    pub fn new<E>(error: E) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        Self::from_boxed(Box::new(error))
+    }
+
+    /// Wraps the given boxed error, reusing it if it's already an `Attached`.
+    pub(crate) fn from_boxed(error: Box<dyn StdError + Send + Sync + 'static>) -> Self {
+        match error.downcast::<Self>() {
+            Ok(attached) => *attached,
+            Err(other) => Self {
+                error: other,
+                attachments: Vec::new(),
+            },
+        }
+    }
+
+    /// The error that the attachments are riding on.
+    #[inline]
+    pub fn error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        &*self.error
+    }
+
+    /// Grabs a reference to the inner error, if it's of the given type.
+    #[inline]
+    pub fn downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref::<E>()
+    }
+
+    /// Throws away the attachments, returning the inner error.
+    #[inline]
+    pub fn into_inner(self) -> Box<dyn StdError + Send + Sync + 'static> {
+        self.error
+    }
+
+    /// Finds the most recent attachment of the given type.
+    #[inline]
+    pub fn request_ref<A: 'static>(&self) -> Option<&A> {
+        self.attachments
+            .iter()
+            .rev()
+            .find_map(|a| a.value.downcast_ref::<A>())
+    }
+
+    /// Every printable attachment, oldest first.
+    pub(crate) fn printable(&self) -> impl Iterator<Item = Printable<'_>> {
+        self.attachments
+            .iter()
+            .filter(|a| a.display.is_some())
+            .map(Printable)
+    }
+
+    pub(crate) fn push(&mut self, attachment: Attachment) {
+        self.attachments.push(attachment);
+    }
+}
+
+impl Debug for Attached {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Attached")
+            .field("error", &self.error)
+            .field("attachments", &self.attachments.len())
+            .finish()
+    }
+}
+
+impl Display for Attached {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl StdError for Attached {
+    #[inline]
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.error.source()
+    }
+}
+
+/// Attaches extra information to an error.
+///
+/// Attaching to an error that's already [`Attached`] adds to its list,
+/// rather than wrapping it again.
+pub trait Attach {
+    /// Attaches a value that can later be found with
+    /// [`Report::request_ref`](crate::Report::request_ref).
+    #[must_use]
+    fn attach<A>(self, attachment: A) -> Attached
+    where
+        A: Send + Sync + 'static;
+
+    /// Attaches a value that's also printed alongside the error in a
+    /// [`Report`](crate::Report).
+    #[must_use]
+    fn attach_printable<A>(self, attachment: A) -> Attached
+    where
+        A: Display + Send + Sync + 'static;
+}
+
+impl<E> Attach for E
+where
+    E: StdError + Send + Sync + 'static,
+{
+    #[inline]
+    fn attach<A>(self, attachment: A) -> Attached
+    where
+        A: Send + Sync + 'static,
+    {
+        let mut attached = Attached::new(self);
+        attached.push(Attachment::new(attachment));
+        attached
+    }
+
+    #[inline]
+    fn attach_printable<A>(self, attachment: A) -> Attached
+    where
+        A: Display + Send + Sync + 'static,
+    {
+        let mut attached = Attached::new(self);
+        attached.push(Attachment::new_printable(attachment));
+        attached
+    }
+}
+
+/// Some value attached to an error.
+pub(crate) struct Attachment {
+    value: Box<dyn Any + Send + Sync>,
+    display: Option<fn(&(dyn Any + Send + Sync), &mut fmt::Formatter<'_>) -> fmt::Result>,
+}
+
+impl Attachment {
+    pub(crate) fn new<A: Send + Sync + 'static>(value: A) -> Self {
+        Self {
+            value: Box::new(value),
+            display: None,
+        }
+    }
+
+    pub(crate) fn new_printable<A: Display + Send + Sync + 'static>(value: A) -> Self {
+        Self {
+            value: Box::new(value),
+            display: Some(display_as::<A>),
+        }
+    }
+}
+
+/// Displays an attachment, given that we remember its type.
+fn display_as<A: Display + 'static>(
+    value: &(dyn Any + Send + Sync),
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    value
+        .downcast_ref::<A>()
+        .map_or(Ok(()), |attachment| Display::fmt(attachment, f))
+}
+
+/// A printable attachment, ready to be displayed.
+pub(crate) struct Printable<'att>(&'att Attachment);
+
+impl Display for Printable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .display
+            .map_or(Ok(()), |display| display(&*self.0.value, f))
+    }
+}
@@ -0,0 +1,243 @@
+//! # Catalog
+//!
+//! Describing every error a type can produce, for documentation.
+
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString as _};
+#[cfg(feature = "alloc")]
+use core::fmt::Write as _;
+
+/// A description of one variant of an error type.
+///
+/// With `#[error(catalog)]` on the enum, `#[derive(Error)]` lists these for
+/// every variant in `YourError::ERROR_CATALOG`. You can turn them into Markdown
+/// with [`catalog_markdown`], or into JSON (or anything else) with the
+/// `serde` feature:
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use pisserror::Error;
+/// use std::error::Error;
+///
+/// #[derive(Debug, Error)]
+/// #[error(catalog, status = 500)]
+/// enum DbError {
+///     /// The key isn't in the table.
+///     #[error("no value for the key `{key}`")]
+///     #[error(status = 404)]
+///     KeyNotFound { key: String },
+///     /// Something went wrong while reading the table.
+///     #[error(transparent)]
+///     Io(#[from] std::io::Error),
+/// }
+///
+/// let info = &DbError::ERROR_CATALOG[0];
+/// assert_eq!(info.name(), "KeyNotFound");
+/// assert_eq!(info.message(), Some("no value for the key `{key}`"));
+/// assert_eq!(info.fields()[0].ty(), "String");
+/// assert_eq!(info.doc(), "The key isn't in the table.");
+/// assert_eq!(info.status(), Some(404));
+///
+/// // `transparent` variants don't have their own message
+/// assert_eq!(DbError::ERROR_CATALOG[1].message(), None);
+/// assert_eq!(DbError::ERROR_CATALOG[1].status(), Some(500));
+///
+/// println!("{}", pisserror::catalog_markdown("DbError", DbError::ERROR_CATALOG));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ErrorInfo {
+    name: &'static str,
+    code: Option<i128>,
+    message: Option<&'static str>,
+    fields: &'static [FieldInfo],
+    doc: &'static str,
+    status: Option<u16>,
+    exit_code: Option<u8>,
+}
+
+impl ErrorInfo {
+    /// Creates an entry. Used by `#[derive(Error)]`.
+    #[doc(hidden)]
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        name: &'static str,
+        code: Option<i128>,
+        message: Option<&'static str>,
+        fields: &'static [FieldInfo],
+        doc: &'static str,
+        status: Option<u16>,
+        exit_code: Option<u8>,
+    ) -> Self {
+        Self {
+            name,
+            code,
+            message,
+            fields,
+            doc,
+            status,
+            exit_code,
+        }
+    }
+
+    /// The variant's name.
+    #[inline]
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The variant's raw code, from its `errno` option.
+    #[inline]
+    #[must_use]
+    pub const fn code(&self) -> Option<i128> {
+        self.code
+    }
+
+    /// The variant's `#[error(...)]` string, without any fields filled in.
+    /// Variants without one (like `transparent` ones) don't have this.
+    #[inline]
+    #[must_use]
+    pub const fn message(&self) -> Option<&'static str> {
+        self.message
+    }
+
+    /// The variant's fields, in order. Unnamed fields are named by their
+    /// position, like `"0"`.
+    #[inline]
+    #[must_use]
+    pub const fn fields(&self) -> &'static [FieldInfo] {
+        self.fields
+    }
+
+    /// The variant's doc comment, without the `///`s. This is empty if it
+    /// doesn't have one.
+    #[inline]
+    #[must_use]
+    pub const fn doc(&self) -> &'static str {
+        self.doc
+    }
+
+    /// The variant's HTTP status code, from its (or the enum's) `status`
+    /// option.
+    #[inline]
+    #[must_use]
+    pub const fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    /// The variant's process exit code, from its (or the enum's)
+    /// `exit_code` option.
+    #[inline]
+    #[must_use]
+    pub const fn exit_code(&self) -> Option<u8> {
+        self.exit_code
+    }
+}
+
+/// A description of one field of an error variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FieldInfo {
+    name: &'static str,
+    ty: &'static str,
+}
+
+impl FieldInfo {
+    /// Creates an entry. Used by `#[derive(Error)]`.
+    #[doc(hidden)]
+    #[inline]
+    #[must_use]
+    pub const fn new(name: &'static str, ty: &'static str) -> Self {
+        Self { name, ty }
+    }
+
+    /// The field's name, or its position for unnamed fields.
+    #[inline]
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The field's type, as written in the enum.
+    #[inline]
+    #[must_use]
+    pub const fn ty(&self) -> &'static str {
+        self.ty
+    }
+}
+
+/// Writes an error type's catalog as a Markdown section, with a table
+/// describing each variant.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn catalog_markdown(type_name: &str, catalog: &[ErrorInfo]) -> String {
+    /// Keeps text from breaking out of its table cell.
+    fn cell(text: &str) -> String {
+        text.trim().replace('|', "\\|").replace('\n', "<br>")
+    }
+
+    let mut markdown = String::new();
+    _ = writeln!(markdown, "## `{type_name}`\n");
+    markdown.push_str("| Variant | Code | Status | Exit code | Message | Fields | Description |\n");
+    markdown.push_str("| --- | --- | --- | --- | --- | --- | --- |\n");
+
+    for info in catalog {
+        let fields = info
+            .fields
+            .iter()
+            .map(|field| alloc::format!("`{}: {}`", field.name, cell(field.ty)))
+            .collect::<alloc::vec::Vec<_>>()
+            .join(", ");
+
+        _ = writeln!(
+            markdown,
+            "| `{}` | {} | {} | {} | {} | {} | {} |",
+            info.name,
+            info.code.map(|code| code.to_string()).unwrap_or_default(),
+            info.status
+                .map(|status| status.to_string())
+                .unwrap_or_default(),
+            info.exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+            info.message.map(cell).unwrap_or_default(),
+            fields,
+            cell(info.doc),
+        );
+    }
+
+    markdown
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorInfo {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct as _;
+
+        let mut state = serializer.serialize_struct("ErrorInfo", 7)?;
+        state.serialize_field("name", self.name)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("fields", self.fields)?;
+        state.serialize_field("doc", self.doc)?;
+        state.serialize_field("status", &self.status)?;
+        state.serialize_field("exit_code", &self.exit_code)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldInfo {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct as _;
+
+        let mut state = serializer.serialize_struct("FieldInfo", 2)?;
+        state.serialize_field("name", self.name)?;
+        state.serialize_field("type", self.ty)?;
+        state.end()
+    }
+}
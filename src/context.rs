@@ -0,0 +1,76 @@
+//! # Context
+//!
+//! Building errors from context selectors, like `snafu` does.
+
+/// A context selector: everything needed to make an error, except its
+/// source.
+///
+/// With `#[error(context_selectors)]`, `#[derive(Error)]` makes a selector
+/// for each variant, named after it with `Snafu` on the end. The variant's
+/// fields become the selector's fields, except for its source (the
+/// `#[from]` or `#[source]` field), which [`ContextExt::context`] fills in.
+/// An `Option` source takes the error inside it, and a `via` source takes
+/// whatever `via` converts from. Each field takes anything that converts
+/// `Into` its type:
+///
+/// ```
+/// use pisserror::{ContextExt as _, Error};
+/// use std::{error::Error, io, path::PathBuf};
+///
+/// #[derive(Debug, Error)]
+/// #[error(context_selectors)]
+/// pub enum ConfigError {
+///     #[error("couldn't read `{}`", path.display())]
+///     Read {
+///         path: PathBuf,
+///         #[source]
+///         source: io::Error,
+///     },
+///     #[error("port {port} is taken")]
+///     PortTaken { port: u16 },
+/// }
+///
+/// fn read(path: &str) -> Result<String, ConfigError> {
+///     std::fs::read_to_string(path).context(ReadSnafu { path })
+/// }
+///
+/// let err = read("/no/such/config.toml").unwrap_err();
+/// assert!(matches!(err, ConfigError::Read { .. }));
+/// assert!(err.source().is_some());
+///
+/// // variants without a source can be built (or returned) directly
+/// let taken: Result<(), _> = PortTakenSnafu { port: 8080_u16 }.fail();
+/// assert_eq!(taken.unwrap_err().to_string(), "port 8080 is taken");
+/// ```
+pub trait IntoError<E> {
+    /// The source error this selector needs.
+    type Source;
+
+    /// Makes the error, using the given source.
+    fn into_error(self, source: Self::Source) -> E;
+}
+
+/// Adds a `context` method to `Result`s, for filling in context selectors.
+pub trait ContextExt<T, E> {
+    /// Makes a bigger error out of the error, if there is one, using a
+    /// context selector from `#[error(context_selectors)]`.
+    ///
+    /// See [`IntoError`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the new error, if there was an error.
+    fn context<C, E2>(self, selector: C) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = E>;
+}
+
+impl<T, E> ContextExt<T, E> for Result<T, E> {
+    #[inline]
+    fn context<C, E2>(self, selector: C) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = E>,
+    {
+        self.map_err(|e| selector.into_error(e))
+    }
+}
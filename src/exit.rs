@@ -0,0 +1,105 @@
+//! # Exit
+//!
+//! Mapping errors to process exit codes, for command-line tools.
+
+#[cfg(feature = "std")]
+use std::process::{ExitCode, Termination};
+
+#[cfg(feature = "std")]
+use crate::report::{Report, Reportable};
+
+/// An error that knows which exit code the process should end with.
+///
+/// `#[derive(Error)]` implements this when the enum or any of its variants
+/// has an `exit_code` option. The codes from BSD's `sysexits.h` are a good
+/// place to start:
+///
+/// ```
+/// use pisserror::{Error, ProcessExit};
+/// use std::error::Error;
+///
+/// #[derive(Debug, Error)]
+/// #[error(exit_code = 70)]
+/// enum CliError {
+///     #[error("couldn't write the output file")]
+///     #[error(exit_code = 74)]
+///     Write(#[from] std::io::Error),
+///     #[error("the config file is malformed")]
+///     #[error(exit_code = 78)]
+///     Config,
+///     #[error("something went wrong")]
+///     Internal,
+/// }
+///
+/// assert_eq!(CliError::Config.exit_code(), 78);
+///
+/// // variants without an `exit_code` use the enum's
+/// assert_eq!(CliError::Internal.exit_code(), 70);
+/// ```
+///
+/// Without an enum-level `exit_code`, variants without one exit with `1`.
+pub trait ProcessExit {
+    /// The code the process should exit with because of this error.
+    fn exit_code(&self) -> u8;
+}
+
+/// The result of a command-line program, for returning from `main`.
+///
+/// When `main` returns an `Exit` holding an error, the error is printed to
+/// `stderr` as a pretty [`Report`], and the process exits with the error's
+/// [`ProcessExit::exit_code`]. Otherwise, the process exits successfully.
+///
+/// ```
+/// use pisserror::{Error, Exit};
+/// use std::error::Error;
+///
+/// #[derive(Debug, Error)]
+/// enum CliError {
+///     #[error("no input files were given")]
+///     #[error(exit_code = 66)]
+///     NoInput,
+/// }
+///
+/// fn run() -> Result<(), CliError> {
+///     Ok(())
+/// }
+///
+/// fn main() -> Exit<CliError> {
+///     run().into()
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct Exit<E>(pub Result<(), E>);
+
+#[cfg(feature = "std")]
+impl<E> From<Result<(), E>> for Exit<E> {
+    #[inline]
+    fn from(result: Result<(), E>) -> Self {
+        Self(result)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> From<E> for Exit<E> {
+    #[inline]
+    fn from(error: E) -> Self {
+        Self(Err(error))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: ProcessExit + Reportable> Termination for Exit<E> {
+    #[inline]
+    #[allow(clippy::print_stderr)]
+    fn report(self) -> ExitCode {
+        match self.0 {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                let code = error.exit_code();
+                eprintln!("Error: {:#}", Report::new(error));
+                ExitCode::from(code)
+            }
+        }
+    }
+}
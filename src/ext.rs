@@ -0,0 +1,113 @@
+//! # Ext
+//!
+//! Extension methods for `Result`s holding an error.
+
+use core::fmt::Display;
+
+#[cfg(any(feature = "log", feature = "tracing"))]
+use crate::Severity;
+#[cfg(feature = "tracing")]
+use crate::Trace;
+use crate::{
+    attach::{Attach as _, Attached},
+    StdError,
+};
+
+/// Extra methods on `Result`s that hold an error.
+pub trait ResultExt<T, E> {
+    /// Attaches a value to the error, if there is one.
+    ///
+    /// See [`Attach::attach`](crate::Attach::attach).
+    ///
+    /// # Errors
+    ///
+    /// Passes along the error, if there was one.
+    fn attach<A>(self, attachment: A) -> Result<T, Attached>
+    where
+        A: Send + Sync + 'static;
+
+    /// Attaches a printable value to the error, if there is one.
+    ///
+    /// See [`Attach::attach_printable`](crate::Attach::attach_printable).
+    ///
+    /// # Errors
+    ///
+    /// Passes along the error, if there was one.
+    fn attach_printable<A>(self, attachment: A) -> Result<T, Attached>
+    where
+        A: Display + Send + Sync + 'static;
+
+    /// Logs the error's message at its [`Severity::level`], if there is an
+    /// error, then passes the result along untouched.
+    ///
+    /// `#[derive(Error)]` only implements [`Severity`] for enums with a `level`
+    /// option somewhere, so give yours one (like `#[error(level = error)]`).
+    ///
+    /// # Errors
+    ///
+    /// Passes along the error, if there was one.
+    #[cfg(feature = "log")]
+    #[must_use]
+    fn log_err(self) -> Self
+    where
+        E: Severity;
+
+    /// Records the error as an event at its [`Severity::level`], if there is
+    /// an error, then passes the result along untouched.
+    ///
+    /// See [`Trace::trace`].
+    ///
+    /// # Errors
+    ///
+    /// Passes along the error, if there was one.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    fn trace_err(self) -> Self
+    where
+        E: Trace + Severity;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E>
+where
+    E: StdError + Send + Sync + 'static,
+{
+    #[inline]
+    fn attach<A>(self, attachment: A) -> Result<T, Attached>
+    where
+        A: Send + Sync + 'static,
+    {
+        self.map_err(|e| e.attach(attachment))
+    }
+
+    #[inline]
+    fn attach_printable<A>(self, attachment: A) -> Result<T, Attached>
+    where
+        A: Display + Send + Sync + 'static,
+    {
+        self.map_err(|e| e.attach_printable(attachment))
+    }
+
+    #[cfg(feature = "log")]
+    #[inline]
+    fn log_err(self) -> Self
+    where
+        E: Severity,
+    {
+        if let Err(ref e) = self {
+            log::log!(log::Level::from(e.level()), "{e}");
+        }
+        self
+    }
+
+    #[cfg(feature = "tracing")]
+    #[inline]
+    fn trace_err(self) -> Self
+    where
+        E: Trace + Severity,
+    {
+        if let Err(ref e) = self {
+            e.trace(e.level().into());
+        }
+        self
+    }
+}
@@ -0,0 +1,57 @@
+//! # L10n
+//!
+//! Localizing error messages with a catalog, like Fluent or gettext.
+
+use alloc::string::String;
+use core::fmt::Display;
+
+/// A catalog of translated messages.
+///
+/// With the `i18n` feature, a variant can name its message with the `l10n`
+/// option. `#[derive(Error)]` then makes a `localize` method, which asks a
+/// `MessageSource` for that message, passing each field as a named
+/// argument, so each one must be `Display`. Unnamed fields are named like
+/// `_0`, and fields marked with `#[error(skip_l10n)]` are left out. The
+/// usual `#[error("...")]` message is used if the catalog doesn't have one,
+/// or if the variant has no `l10n` option.
+///
+/// The enum also gets a `MESSAGE_IDS` constant, listing every ID its
+/// variants use, so you can check that a catalog is complete.
+///
+/// ```
+/// use pisserror::{Error, MessageSource};
+/// use std::{error::Error, fmt::Display};
+///
+/// #[derive(Debug, Error)]
+/// enum DbError {
+///     #[error("no value for the key `{key}`")]
+///     #[error(l10n = "db-key-not-found")]
+///     KeyNotFound { key: String },
+///     #[error("the database is locked")]
+///     Locked,
+/// }
+///
+/// /// A tiny "catalog" that only knows one message.
+/// struct German;
+///
+/// impl MessageSource for German {
+///     fn message(&self, id: &str, args: &[(&str, &dyn Display)]) -> Option<String> {
+///         match (id, args) {
+///             ("db-key-not-found", &[("key", key)]) => Some(format!("kein Wert für `{key}`")),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let err = DbError::KeyNotFound { key: "user".into() };
+/// assert_eq!(err.localize(&German), "kein Wert für `user`");
+///
+/// // without a translation, the English message is used
+/// assert_eq!(DbError::Locked.localize(&German), "the database is locked");
+/// assert_eq!(DbError::MESSAGE_IDS, ["db-key-not-found"]);
+/// ```
+pub trait MessageSource {
+    /// Formats the message with the given ID, or returns `None` if there
+    /// isn't one.
+    fn message(&self, id: &str, args: &[(&str, &dyn Display)]) -> Option<String>;
+}
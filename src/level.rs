@@ -0,0 +1,81 @@
+//! # Level
+//!
+//! How severe an error is, for logging.
+
+/// How severe an error is, from least to most.
+///
+/// These match the levels of the `log` and `tracing` crates, and convert
+/// into both when their features are on.
+#[allow(clippy::exhaustive_enums)] // both logging crates have exactly these
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Very noisy details.
+    Trace,
+    /// Details for debugging.
+    Debug,
+    /// Something worth knowing, but expected.
+    Info,
+    /// Something's off, but the program can keep going.
+    Warn,
+    /// Something failed.
+    Error,
+}
+
+#[cfg(feature = "log")]
+impl From<Level> for log::Level {
+    #[inline]
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Trace => Self::Trace,
+            Level::Debug => Self::Debug,
+            Level::Info => Self::Info,
+            Level::Warn => Self::Warn,
+            Level::Error => Self::Error,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl From<Level> for tracing::Level {
+    #[inline]
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Trace => Self::TRACE,
+            Level::Debug => Self::DEBUG,
+            Level::Info => Self::INFO,
+            Level::Warn => Self::WARN,
+            Level::Error => Self::ERROR,
+        }
+    }
+}
+
+/// An error that knows how severe it is.
+///
+/// `#[derive(Error)]` implements this when the enum or any of its variants
+/// has a `level` option, or when the enum has `#[error(trace)]`.
+/// Not every error is worth waking someone up for:
+///
+/// ```
+/// use pisserror::{Error, Level, Severity};
+/// use std::error::Error;
+///
+/// #[derive(Debug, Error)]
+/// enum CacheError {
+///     #[error("no cached entry for `{_0}`")]
+///     #[error(level = debug)]
+///     Miss(String),
+///     #[error("the disk is full")]
+///     DiskFull,
+/// }
+///
+/// assert_eq!(CacheError::Miss(String::from("piss")).level(), Level::Debug);
+///
+/// // variants without a `level` are errors
+/// assert_eq!(CacheError::DiskFull.level(), Level::Error);
+/// ```
+///
+/// An enum-level `level` option changes the default for its variants.
+pub trait Severity {
+    /// How severe this error is.
+    fn level(&self) -> Level;
+}
@@ -26,6 +26,43 @@ pub enum DatabaseError {
 }
 ```
 
+You'll still need to `#[derive(Debug)]` yourself, though. Or, if you'd rather `Debug` show your error messages (which is what you see when `main` returns an `Err`), you can add `#[error(debug = display)]` to the enum. Using `#[error(debug = report)]` also lists each source below the message. Either way, the alternate form (`{:#?}`) still shows the enum's structure:
+
+```
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Error)]
+#[error(debug = report)]
+pub enum ConfigError {
+    #[error("failed to read the config file")]
+    Read(#[from] std::io::Error),
+}
+
+let err = ConfigError::from(std::io::Error::other("permission denied"));
+assert_eq!(
+    format!("{err:?}"),
+    "failed to read the config file\n\nCaused by:\n      permission denied"
+);
+```
+
+To print an error alongside all of its sources, wrap it in a `Report`. And, if you're writing an application that doesn't care about the exact type of its errors, `AnyError` can hold any of them (with the `alloc` feature, which `std` enables by default):
+
+```
+# #[cfg(feature = "alloc")] {
+use pisserror::{AnyError, Report, WrapErr as _};
+
+fn read_config() -> Result<String, AnyError> {
+    let config = std::fs::read_to_string("/does/not/exist.toml")
+        .wrap_err("failed to read the config file")?;
+    Ok(config)
+}
+
+let err = read_config().unwrap_err();
+assert!(err.chain().nth(1).unwrap().is::<std::io::Error>());
+# }
+```
+
 Also, you may wish to note that `pisserror` works with `#![no_std]`/embedded projects! Just ask `cargo add` to not use default features, like `cargo add pisserror --no-default-features`.
 
 Alternatively, you can add it to `Cargo.toml` by adding `default-features = false`:
@@ -35,6 +72,212 @@ Alternatively, you can add it to `Cargo.toml` by adding `default-features = fals
 pisserror = { version = (your version), default-features = false }
 ```
 
+Without its default features, `pisserror` never allocates. If your target has an allocator but no `std`, you can turn on the `alloc` feature to get `AnyError` and support for boxed sources, like `Box<dyn Error + Send + Sync>`:
+
+```toml
+[dependencies]
+pisserror = { version = (your version), default-features = false, features = ["alloc"] }
+```
+
+If you need to send your errors somewhere, like in a JSON response or a structured log, turn on the `serde` feature and add `#[error(serialize)]` to your enum. That implements `Serialize`, writing each variant's name, message, fields, and sources. Sources are written with their messages, so they don't need to be `Serialize` themselves. To leave out a field (like a password), mark it with `#[error(skip_serializing)]`.
+
+For example, `DatabaseError::KeyNotFound(String::from("piss"))` from above would look like this in JSON:
+
+```json
+{
+    "kind": "KeyNotFound",
+    "message": "key `piss` has no matching data records",
+    "fields": { "0": "piss" },
+    "source": null
+}
+```
+
+For HTTP services, variants can describe themselves as [problem details](https://www.rfc-editor.org/rfc/rfc9457) using the `status`, `type_uri`, and `title` options. Put them in another `#[error(...)]` attribute next to the message, and you'll get a `to_problem_details` method (with the `alloc` feature). Its `detail` is the variant's message, its `status` is the same one `HttpStatus` gives (see below), and, with the `serde` feature, it serializes as `application/problem+json`:
+
+```
+# #[cfg(feature = "alloc")] {
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("no user named `{_0}`")]
+    #[error(status = 404, type_uri = "https://example.com/probs/no-user", title = "No such user")]
+    NoUser(String),
+    #[error("the database is down")]
+    #[error(status = 503)]
+    Database(#[from] std::io::Error),
+}
+
+let problem = ApiError::NoUser(String::from("barrett")).to_problem_details();
+assert_eq!(problem.status, Some(404));
+assert_eq!(problem.detail.as_deref(), Some("no user named `barrett`"));
+# }
+```
+
+If you only need the status code, any `status` option (or `transparent` variant) also implements the `HttpStatus` trait. Add `#[error(status = ...)]` to the enum itself to change the status of variants without one (which is `500` otherwise). `transparent` variants without a `status` use their inner error's, if it implements `HttpStatus` too.
+
+Command-line tools can do the same with exit codes. Variants (and the enum itself, as a default) take an `exit_code` option, which implements the `ProcessExit` trait. Then, return an `Exit` from `main` to print a `Report` of any error and exit with its code:
+
+```
+# #[cfg(feature = "std")]
+# mod example {
+use pisserror::{Error, Exit};
+use std::error::Error;
+
+#[derive(Debug, Error)]
+#[error(exit_code = 70)]
+pub enum CliError {
+    #[error("couldn't read the input file")]
+    #[error(exit_code = 66)]
+    NoInput(#[from] std::io::Error),
+    #[error("something went wrong")]
+    Internal,
+}
+
+fn run() -> Result<(), CliError> {
+    Ok(())
+}
+
+fn main() -> Exit<CliError> {
+    run().into()
+}
+# }
+# fn main() {}
+```
+
+If you implement traits like `Read` or `Write`, you'll need to return an `std::io::Error`. Giving any variant (or the enum) an `io_kind` option implements `From<YourError> for std::io::Error`, using that `ErrorKind` (or `Other`, by default). Your error stays inside, so you can get it back with `get_ref` or `into_inner`. However, `transparent` variants around an `io::Error` just hand that error back, so nothing gets wrapped twice:
+
+```
+# #[cfg(feature = "std")] {
+use pisserror::Error;
+use std::{error::Error, io};
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("no such key: `{_0}`")]
+    #[error(io_kind = NotFound)]
+    NoKey(String),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+let err = io::Error::from(StoreError::NoKey(String::from("piss")));
+assert_eq!(err.kind(), io::ErrorKind::NotFound);
+assert!(err.get_ref().unwrap().is::<StoreError>());
+# }
+```
+
+For C APIs, give every variant an `errno` option. You'll get an `as_raw` method, a `TryFrom` implementation for unit variants, and a `RAW_CODES` table of each variant's code and message. For C, there's also a `#[no_mangle] extern "C" fn pisserror_strerror(code)`, which looks up a code's message in that table (rename it with the enum's `strerror` option). Codes are `i32`s, unless you pick another integer type with the enum's `repr` option:
+
+```
+use pisserror::Error;
+use std::error::Error;
+
+#[derive(Debug, Error)]
+#[error(repr = i32)]
+pub enum FfiError {
+    #[error("an argument was invalid")]
+    #[error(errno = -22)]
+    InvalidArgument,
+    #[error("no such file: {path}")]
+    #[error(errno = -2)]
+    NotFound { path: String },
+}
+
+assert_eq!(FfiError::InvalidArgument.as_raw(), -22);
+assert!(matches!(FfiError::try_from(-22), Ok(FfiError::InvalidArgument)));
+assert_eq!(FfiError::RAW_CODES[1].message().to_str(), Ok("no such file: {path}"));
+```
+
+Not every error is an emergency. Give a variant (or the enum) a `level` option, like `#[error(level = warn)]`, and its `Severity::level` will say so. Variants default to the enum's level, or `Level::Error`. With the `log` feature, `ResultExt::log_err` logs the message of any error with a `level` option at that level as it goes by:
+
+```
+use pisserror::{Error, Level, Severity};
+use std::error::Error;
+
+#[derive(Debug, Error)]
+#[error(level = warn)]
+pub enum SyncError {
+    #[error("the server is busy, so we'll try again later")]
+    #[error(level = info)]
+    Busy,
+    #[error("couldn't reach the server")]
+    Offline,
+}
+
+assert_eq!(SyncError::Busy.level(), Level::Info);
+assert_eq!(SyncError::Offline.level(), Level::Warn);
+```
+
+A `#[from]` field can also list the types it comes from, like `#[from(ParseIntError, ParseFloatError)]`. Each one gets its own `From`, which converts it into the field with `Into`, so several errors can share one boxed variant. To convert some other way, add `via`, like `#[from(via = Arc::new)] Io(Arc<io::Error>)`. Without a list, `via` takes whatever the field's `Box`, `Arc`, or `Rc` holds, as long as it's not a trait object. Either way, `source()` returns the error inside the pointer, so it can still be downcast. Trait objects work the same way, so `Box<dyn Error + Send + Sync>`, `Arc<dyn Error>`, and `Rc<dyn Error>` fields can be sources too.
+
+Variants can hold other fields alongside their source, too. Mark it with `#[source]` instead of `#[from]`, and `source()` will return it. A `#[source]` field can also be an `Option`, like `last_error: Option<io::Error>`, for a cause that might not be there. To fill those variants in without a big `map_err`, add `#[error(context_selectors)]` to the enum. Each variant then gets a selector, like `ReadSnafu { path }`, holding every field but the source, and `ContextExt::context` adds the source:
+
+```
+use pisserror::{ContextExt as _, Error};
+use std::{error::Error, io, path::PathBuf};
+
+#[derive(Debug, Error)]
+#[error(context_selectors)]
+pub enum ConfigError {
+    #[error("couldn't read `{}`", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+fn read_config(path: &str) -> Result<String, ConfigError> {
+    std::fs::read_to_string(path).context(ReadSnafu { path })
+}
+
+let err = read_config("/no/such/config.toml").unwrap_err();
+assert!(err.source().is_some());
+```
+
+For variants with lots of fields, `#[error(constructors)]` gives each variant a function named after it in snake_case. Each argument takes anything that goes `Into` its field, so `MyError::malformed_header("Host", got)` is enough. `#[from]` variants get one too, taking their source like `From::from` would.
+
+To keep callers from `match`ing on your variants, `#[error(accessors)]` gives each variant an `is_*` method, like `is_key_not_found()`. Variants with fields also get `as_*`, which borrows them (`Option<&String>`), and `into_*`, which takes them out (`Result<String, Self>`). Variants with more than one field give them back as a tuple.
+
+When one error type is another plus a few more variants, `error_set!` can write both. Each set becomes an enum that derives `Error`, and a set made from other sets with `||` gets a copy of their variants. It can be made `From` each of them, and each of them can be taken back out with `TryFrom`:
+
+```
+use pisserror::error_set;
+use std::error::Error;
+
+error_set! {
+    #[derive(Debug)]
+    pub ParseError = {
+        #[error("unexpected end of input")]
+        Eof,
+        #[error("bad token `{_0}`")]
+        BadToken(char),
+    };
+
+    #[derive(Debug)]
+    pub LoadError = ParseError || {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    };
+}
+
+let err = LoadError::from(ParseError::BadToken('%'));
+assert!(matches!(err, LoadError::BadToken('%')));
+assert!(matches!(ParseError::try_from(err), Ok(ParseError::BadToken('%'))));
+```
+
+With the `tracing` feature, add `#[error(trace)]` to an enum to implement `Trace`, which records an error as a structured event: the variant's name, its message, its `errno` code, and each of its fields (except the `skip_serializing` ones). `ResultExt::trace_err` does that for you at the error's level as it goes by.
+
+To document every error a service can produce, turn on the `catalog` feature and add `#[error(catalog)]` to your enums. Each one then gets an `ERROR_CATALOG` of `ErrorInfo`s, listing every variant's name, `errno` code, message, fields, doc comment, and status and exit codes. `catalog_markdown` turns one into a Markdown table, and with the `serde` feature, you can write them out as JSON instead.
+
+For translated messages, turn on the `i18n` feature and give variants an ID with `#[error(l10n = "db-key-not-found")]`. You'll get a `localize` method that looks the message up in any `MessageSource` (like a Fluent bundle), passing each field as a named argument (except the ones marked with `#[error(skip_l10n)]`), and a `MESSAGE_IDS` list for checking your catalogs. The `#[error("...")]` message stays as the English fallback.
+
+On embedded targets that log through `defmt`, turn on the `defmt` feature and add `#[error(defmt)]` to your enum. That implements `defmt::Format` using the same messages, translated to `defmt`'s syntax. `defmt` can't pad or align values, so messages using those format specs won't compile. Your crate also needs to depend on `defmt` itself.
+
+The `ufmt` feature works the same way: `#[error(ufmt)]` implements `ufmt::uDisplay` and `ufmt::uDebug` from your messages, next to the usual `Display`. `ufmt` only supports `{}`, `{:?}`, `{:#?}`, and hex specs like `{:#06x}`, so anything else is a compile error pointing at the message. Your crate needs to depend on `ufmt`, too.
+
 ## Feature Requests and Problems
 
 If there's something wrong or missing, please create a GitHub issue! Make sure to thoroughly describe your intentions.
@@ -60,7 +303,82 @@ There are only a few steps to releasing this crate.
 */
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(doctest)]
 pub mod _doctests;
 
-pub use pisserror_macros::Error;
+#[cfg(feature = "alloc")]
+mod any;
+#[cfg(feature = "alloc")]
+mod attach;
+#[cfg(feature = "catalog")]
+mod catalog;
+mod context;
+mod exit;
+#[cfg(feature = "alloc")]
+mod ext;
+#[cfg(feature = "i18n")]
+mod l10n;
+mod level;
+#[cfg(feature = "alloc")]
+mod multi;
+#[cfg(feature = "alloc")]
+mod problem;
+mod raw;
+mod report;
+#[cfg(feature = "serde")]
+mod ser;
+mod status;
+#[cfg(feature = "tracing")]
+mod trace;
+
+#[cfg(feature = "alloc")]
+pub use any::{AnyError, WrapErr};
+#[cfg(feature = "alloc")]
+pub use attach::{Attach, Attached};
+#[cfg(all(feature = "catalog", feature = "alloc"))]
+pub use catalog::catalog_markdown;
+#[cfg(feature = "catalog")]
+pub use catalog::{ErrorInfo, FieldInfo};
+pub use context::{ContextExt, IntoError};
+#[cfg(feature = "std")]
+pub use exit::Exit;
+pub use exit::ProcessExit;
+#[cfg(feature = "alloc")]
+pub use ext::ResultExt;
+#[cfg(feature = "i18n")]
+pub use l10n::MessageSource;
+pub use level::{Level, Severity};
+#[cfg(feature = "alloc")]
+pub use multi::MultiError;
+pub use pisserror_macros::{error_set, Error};
+#[cfg(feature = "alloc")]
+pub use problem::ProblemDetails;
+pub use raw::{strerror, RawCode, UnknownCode};
+pub use report::{Chain, Report, Reportable};
+pub use status::HttpStatus;
+#[cfg(feature = "tracing")]
+pub use trace::Trace;
+
+/// Things that code generated by the derive needs to reach.
+///
+/// Nothing in here is covered by semver. Please don't use it directly!
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use crate::ser::{Field, FieldList, Fields, Message, Messages, Source};
+    pub use crate::status::{StatusFallback, StatusOf, StatusViaTrait};
+    #[cfg(feature = "i18n")]
+    pub use alloc::string::{String, ToString};
+    #[cfg(feature = "serde")]
+    pub use serde;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) use core::error::Error as StdError;
+#[cfg(feature = "std")]
+pub(crate) use std::error::Error as StdError;
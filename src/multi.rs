@@ -0,0 +1,165 @@
+//! # Multi
+//!
+//! A collection of errors, for when one isn't enough.
+
+use alloc::vec::{self, Vec};
+use core::{
+    fmt::{self, Display},
+    slice,
+};
+
+use crate::StdError;
+
+/// A list of errors that acts as one error.
+///
+/// This is handy for validation, where you'd like to report every problem at
+/// once instead of stopping at the first one:
+///
+/// ```
+/// use pisserror::MultiError;
+///
+/// fn validate(ports: &[&str]) -> Result<(), MultiError<std::num::ParseIntError>> {
+///     ports
+///         .iter()
+///         .filter_map(|p| p.parse::<u16>().err())
+///         .collect::<MultiError<_>>()
+///         .into_result()
+/// }
+///
+/// assert!(validate(&["80", "443"]).is_ok());
+///
+/// let err = validate(&["eighty", "443", ""]).unwrap_err();
+/// assert_eq!(err.len(), 2);
+/// assert_eq!(
+///     err.to_string(),
+///     "1. invalid digit found in string\n2. cannot parse integer from empty string"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiError<E> {
+    errors: Vec<E>,
+}
+
+impl<E> MultiError<E> {
+    /// Creates an empty list of errors.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    /// Adds an error to the end of the list.
+    #[inline]
+    pub fn push(&mut self, error: E) {
+        self.errors.push(error);
+    }
+
+    /// The number of errors in the list.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Checks if there are no errors in the list.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// An iterator over the errors, in the order they were added.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, E> {
+        self.errors.iter()
+    }
+
+    /// Takes the errors out as a `Vec`.
+    #[inline]
+    pub fn into_vec(self) -> Vec<E> {
+        self.errors
+    }
+
+    /// Turns the list into a `Result`, which is only `Ok` if there are no
+    /// errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` if there are any errors in the list.
+    #[inline]
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<E> Default for MultiError<E> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> From<Vec<E>> for MultiError<E> {
+    #[inline]
+    fn from(errors: Vec<E>) -> Self {
+        Self { errors }
+    }
+}
+
+impl<E> Extend<E> for MultiError<E> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        self.errors.extend(iter);
+    }
+}
+
+impl<E> FromIterator<E> for MultiError<E> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<E> IntoIterator for MultiError<E> {
+    type Item = E;
+    type IntoIter = vec::IntoIter<E>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'errors, E> IntoIterator for &'errors MultiError<E> {
+    type Item = &'errors E;
+    type IntoIter = slice::Iter<'errors, E>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<E: Display> Display for MultiError<E> {
+    /// Prints each error on its own, numbered line.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no errors occurred");
+        }
+
+        for (i, error) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}. {error}", i.saturating_add(1))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: StdError> StdError for MultiError<E> {}
@@ -0,0 +1,173 @@
+//! # Problem
+//!
+//! HTTP problem details ([RFC 9457](https://www.rfc-editor.org/rfc/rfc9457)),
+//! for services that return errors as `application/problem+json`.
+
+use alloc::string::{String, ToString as _};
+use core::fmt::Display;
+
+/// A problem details object, as described by RFC 9457.
+///
+/// You'll usually get one from the `to_problem_details` method that
+/// `#[derive(Error)]` makes when a variant has a `status`, `type_uri`, or
+/// `title` option:
+///
+/// ```
+/// use pisserror::Error;
+/// use std::error::Error;
+///
+/// #[derive(Debug, Error)]
+/// enum ApiError {
+///     #[error("no user with the ID `{_0}`")]
+///     #[error(status = 404, type_uri = "https://example.com/probs/no-user", title = "No such user")]
+///     NoUser(u64),
+///     #[error("something went wrong")]
+///     Internal,
+/// }
+///
+/// let problem = ApiError::NoUser(42).to_problem_details().with_instance("/users/42");
+/// assert_eq!(problem.status, Some(404));
+/// assert_eq!(problem.title.as_deref(), Some("No such user"));
+/// assert_eq!(problem.detail.as_deref(), Some("no user with the ID `42`"));
+///
+/// // variants without any options still get a `detail`, and the same
+/// // status as `HttpStatus` gives them
+/// let problem = ApiError::Internal.to_problem_details();
+/// assert_eq!(problem.type_uri, "about:blank");
+/// assert_eq!(problem.status, Some(500));
+/// ```
+///
+/// With the `serde` feature, this serializes into the JSON object that RFC
+/// 9457 describes. Unset members are left out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProblemDetails {
+    /// A URI identifying the kind of problem. Serialized as `type`.
+    pub type_uri: String,
+    /// A short, human-readable summary of the kind of problem.
+    pub title: Option<String>,
+    /// The HTTP status code for this occurrence of the problem.
+    pub status: Option<u16>,
+    /// A human-readable explanation of this occurrence of the problem.
+    pub detail: Option<String>,
+    /// A URI identifying this occurrence of the problem.
+    pub instance: Option<String>,
+}
+
+impl ProblemDetails {
+    /// The media type for a problem details JSON object.
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+
+    /// The `type` used when there's nothing more specific.
+    pub const BLANK_TYPE: &'static str = "about:blank";
+
+    /// Creates problem details with no members set, other than the blank
+    /// `type`.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            type_uri: String::from(Self::BLANK_TYPE),
+            title: None,
+            status: None,
+            detail: None,
+            instance: None,
+        }
+    }
+
+    /// Creates problem details with the error's message as the `detail`.
+    #[inline]
+    pub fn for_error<E: Display + ?Sized>(error: &E) -> Self {
+        Self {
+            detail: Some(error.to_string()),
+            ..Self::new()
+        }
+    }
+
+    /// Sets the `type` member.
+    #[inline]
+    #[must_use]
+    pub fn with_type<S: Into<String>>(self, type_uri: S) -> Self {
+        Self {
+            type_uri: type_uri.into(),
+            ..self
+        }
+    }
+
+    /// Sets the `title` member.
+    #[inline]
+    #[must_use]
+    pub fn with_title<S: Into<String>>(self, title: S) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Sets the `status` member.
+    #[inline]
+    #[must_use]
+    pub fn with_status(self, status: u16) -> Self {
+        Self {
+            status: Some(status),
+            ..self
+        }
+    }
+
+    /// Sets the `detail` member.
+    #[inline]
+    #[must_use]
+    pub fn with_detail<S: Into<String>>(self, detail: S) -> Self {
+        Self {
+            detail: Some(detail.into()),
+            ..self
+        }
+    }
+
+    /// Sets the `instance` member.
+    #[inline]
+    #[must_use]
+    pub fn with_instance<S: Into<String>>(self, instance: S) -> Self {
+        Self {
+            instance: Some(instance.into()),
+            ..self
+        }
+    }
+}
+
+impl Default for ProblemDetails {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ProblemDetails {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap as _;
+
+        let optional = [
+            ("title", self.title.as_deref()),
+            ("detail", self.detail.as_deref()),
+            ("instance", self.instance.as_deref()),
+        ];
+        let len = optional
+            .iter()
+            .filter(|&&(_, value)| value.is_some())
+            .count()
+            .saturating_add(usize::from(self.status.is_some()))
+            .saturating_add(1);
+
+        let mut map = serializer.serialize_map(Some(len))?;
+        map.serialize_entry("type", &self.type_uri)?;
+        if let Some(status) = self.status {
+            map.serialize_entry("status", &status)?;
+        }
+        for (key, member) in optional {
+            if let Some(value) = member {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
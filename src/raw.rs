@@ -0,0 +1,151 @@
+//! # Raw
+//!
+//! Raw integer codes for errors, for use across FFI boundaries.
+
+use core::{
+    ffi::{c_char, CStr},
+    fmt,
+};
+
+/// An entry in the table of raw codes that `#[derive(Error)]` makes when
+/// variants have an `errno` option.
+///
+/// The table is available as `YourError::RAW_CODES`. It lists every
+/// variant's code, name, and message, so C code can turn a code back into
+/// something readable. Messages are the variant's `#[error(...)]` string,
+/// without any fields filled in.
+///
+/// For C, the enum also gets a `#[no_mangle] extern "C"` function that
+/// looks messages up in the table, named `pisserror_strerror`. If a crate
+/// has more than one of these enums, give each function its own name with
+/// the enum's `strerror` option, like `#[error(strerror = db_strerror)]`:
+///
+/// ```
+/// use core::ffi::CStr;
+/// use pisserror::Error;
+/// use std::error::Error;
+///
+/// #[derive(Debug, Error)]
+/// #[error(repr = i32)]
+/// pub enum FfiError {
+///     #[error("an argument was invalid")]
+///     #[error(errno = -22)]
+///     InvalidArgument,
+///     #[error("no such file: {path}")]
+///     #[error(errno = -2)]
+///     NotFound { path: String },
+/// }
+///
+/// let err = FfiError::NotFound { path: String::from("/piss") };
+/// assert_eq!(err.as_raw(), -2);
+/// assert_eq!(FfiError::try_from(-22).unwrap().as_raw(), -22);
+///
+/// // variants with fields can't be rebuilt from just a code
+/// assert!(FfiError::try_from(-2).is_err());
+///
+/// let message = unsafe { CStr::from_ptr(pisserror_strerror(-2)) };
+/// assert_eq!(message.to_str(), Ok("no such file: {path}"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RawCode<R: 'static> {
+    code: R,
+    name: &'static str,
+    /// Always nul-terminated, with no other nul bytes.
+    message: &'static [u8],
+}
+
+impl<R: Copy> RawCode<R> {
+    /// Creates an entry. Used by `#[derive(Error)]`.
+    ///
+    /// # Panics
+    ///
+    /// The message must end with a nul byte, and have no others. As tables
+    /// are `const`, this panics at compile time.
+    #[doc(hidden)]
+    #[inline]
+    pub const fn new(code: R, name: &'static str, message: &'static [u8]) -> Self {
+        assert!(is_c_string(message), "the message must be a C string");
+
+        Self {
+            code,
+            name,
+            message,
+        }
+    }
+
+    /// The variant's raw code.
+    #[inline]
+    pub const fn code(&self) -> R {
+        self.code
+    }
+
+    /// The variant's name.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The variant's message, as a C string.
+    #[inline]
+    pub const fn message(&self) -> &'static CStr {
+        // SAFETY: `RawCode::new` checked that this is a C string.
+        unsafe { CStr::from_bytes_with_nul_unchecked(self.message) }
+    }
+}
+
+/// Checks that the given bytes end with a nul byte, and have no others.
+const fn is_c_string(bytes: &[u8]) -> bool {
+    match bytes.split_last() {
+        Some((&last, rest)) => last == 0 && has_no_nul(rest),
+        None => false,
+    }
+}
+
+/// Checks that there are no nul bytes in the given bytes.
+const fn has_no_nul(bytes: &[u8]) -> bool {
+    // a loop, since long messages would hit the const recursion limit
+    let mut rest = bytes;
+    while let Some((&first, tail)) = rest.split_first() {
+        if first == 0 {
+            return false;
+        }
+        rest = tail;
+    }
+
+    true
+}
+
+/// A generic "unknown error" message, for codes that aren't in a table.
+const UNKNOWN: &[u8] = b"unknown error\0";
+
+/// Finds the message for a raw code in the given table, as a pointer to a
+/// `'static`, nul-terminated C string. Codes that aren't in the table get
+/// `"unknown error"`.
+///
+/// `#[derive(Error)]` uses this for its `pisserror_strerror` function, but
+/// you can call it yourself, too. See [`RawCode`] for an example.
+#[inline]
+pub fn strerror<R: Copy + PartialEq>(table: &[RawCode<R>], code: R) -> *const c_char {
+    table
+        .iter()
+        .find(|entry| entry.code == code)
+        .map_or(UNKNOWN, |entry| entry.message)
+        .as_ptr()
+        .cast()
+}
+
+/// An error for when no unit variant has the given raw code.
+///
+/// This is the error type for the `TryFrom` implementations made by the
+/// `errno` option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownCode<R>(pub R);
+
+impl<R: fmt::Display> fmt::Display for UnknownCode<R> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no unit variant has the code `{}`", self.0)
+    }
+}
+
+impl<R: fmt::Debug + fmt::Display> crate::StdError for UnknownCode<R> {}
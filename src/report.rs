@@ -0,0 +1,247 @@
+//! # Report
+//!
+//! Renders an error alongside every error in its `source` chain.
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::attach::Attached;
+use crate::StdError;
+
+/// Something that a [`Report`] can render.
+///
+/// This is implemented for every `'static` error type, as well as
+/// [`AnyError`](crate::AnyError) (when the `alloc` feature is enabled).
+pub trait Reportable {
+    /// Grabs the error at the head of the chain.
+    fn as_error(&self) -> &(dyn StdError + 'static);
+}
+
+impl<E: StdError + 'static> Reportable for E {
+    #[inline]
+    fn as_error(&self) -> &(dyn StdError + 'static) {
+        self
+    }
+}
+
+/// Displays an error along with its chain of sources.
+///
+/// By default, a `Report` is printed on one line, like `outer: middle: root`.
+/// Using [`Report::pretty`] (or the alternate flag, `{:#}`) spreads it out
+/// instead:
+///
+/// ```text
+/// outer
+///
+/// Caused by:
+///       0: middle
+///       1: root
+/// ```
+///
+/// `Debug` always uses the pretty form, so returning a `Report` from `main`
+/// gives readable output.
+///
+/// ```
+/// use pisserror::{Error, Report};
+/// use std::error::Error;
+///
+/// #[derive(Debug, Error)]
+/// enum ConfigError {
+///     #[error("failed to read the config file")]
+///     Read(#[from] std::io::Error),
+/// }
+///
+/// let io = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+/// let report = Report::new(ConfigError::from(io));
+///
+/// assert_eq!(report.to_string(), "failed to read the config file: no such file");
+/// ```
+#[derive(Clone)]
+pub struct Report<E> {
+    error: E,
+    pretty: bool,
+}
+
+impl<E: Reportable> Report<E> {
+    /// Creates a new, single-line `Report` around the given error.
+    #[inline]
+    pub const fn new(error: E) -> Self {
+        Self {
+            error,
+            pretty: false,
+        }
+    }
+
+    /// Chooses whether to print each error on its own line.
+    #[inline]
+    #[must_use]
+    pub const fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// The error being reported.
+    #[inline]
+    pub const fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Takes the error back out of the `Report`.
+    #[inline]
+    pub fn into_error(self) -> E {
+        self.error
+    }
+
+    /// An iterator over the reported error and each of its sources.
+    #[inline]
+    pub fn chain(&self) -> Chain<'_> {
+        Chain::new(self.error.as_error())
+    }
+
+    /// Finds the most recent attachment of the given type anywhere in the
+    /// error's chain.
+    ///
+    /// See [`Attach`](crate::Attach) for more information.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn request_ref<A: 'static>(&self) -> Option<&A> {
+        request_ref(self.chain())
+    }
+}
+
+impl<E: Reportable> From<E> for Report<E> {
+    #[inline]
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl<E: Reportable> fmt::Display for Report<E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self.error.as_error(), f, self.pretty || f.alternate())
+    }
+}
+
+impl<E: Reportable> fmt::Debug for Report<E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self.error.as_error(), f, true)
+    }
+}
+
+/// An iterator over an error and each of its sources, starting from the
+/// outermost error.
+#[derive(Clone, Debug)]
+pub struct Chain<'err> {
+    next: Option<&'err (dyn StdError + 'static)>,
+}
+
+impl<'err> Chain<'err> {
+    /// Creates a chain that starts at the given error.
+    #[inline]
+    pub const fn new(head: &'err (dyn StdError + 'static)) -> Self {
+        Self { next: Some(head) }
+    }
+}
+
+impl<'err> Iterator for Chain<'err> {
+    type Item = &'err (dyn StdError + 'static);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.source();
+        Some(current)
+    }
+}
+
+/// Writes out the given error and its sources.
+pub(crate) fn render(
+    head: &(dyn StdError + 'static),
+    f: &mut fmt::Formatter<'_>,
+    pretty: bool,
+) -> fmt::Result {
+    write!(f, "{head}")?;
+
+    if !pretty {
+        write_attachments(head, f, None)?;
+        for cause in Chain::new(head).skip(1) {
+            write!(f, ": {cause}")?;
+            write_attachments(cause, f, None)?;
+        }
+        return Ok(());
+    }
+
+    write_attachments(head, f, Some(2))?;
+
+    let mut causes = Chain::new(head).skip(1).peekable();
+    if causes.peek().is_none() {
+        return Ok(());
+    }
+    f.write_str("\n\nCaused by:")?;
+
+    // only number the causes when there's more than one
+    let numbered = head.source().and_then(StdError::source).is_some();
+    for (i, cause) in causes.enumerate() {
+        if numbered {
+            write!(f, "\n{i:>7}: {cause}")?;
+            write_attachments(cause, f, Some(9))?;
+        } else {
+            write!(f, "\n      {cause}")?;
+            write_attachments(cause, f, Some(6))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes out the printable attachments on an error, if it has any.
+///
+/// With an indent, each attachment gets its own line. Otherwise, they're put
+/// in parentheses.
+#[cfg(feature = "alloc")]
+fn write_attachments(
+    error: &(dyn StdError + 'static),
+    f: &mut fmt::Formatter<'_>,
+    indent: Option<usize>,
+) -> fmt::Result {
+    let Some(attached) = error.downcast_ref::<Attached>() else {
+        return Ok(());
+    };
+
+    if let Some(width) = indent {
+        for attachment in attached.printable() {
+            write!(f, "\n{:width$}- {attachment}", "")?;
+        }
+        return Ok(());
+    }
+
+    for (i, attachment) in attached.printable().enumerate() {
+        f.write_str(if i == 0 { " (" } else { ", " })?;
+        write!(f, "{attachment}")?;
+    }
+    if attached.printable().next().is_some() {
+        f.write_str(")")?;
+    }
+
+    Ok(())
+}
+
+/// Without an allocator, there can't be any attachments.
+#[cfg(not(feature = "alloc"))]
+fn write_attachments(
+    _error: &(dyn StdError + 'static),
+    _f: &mut fmt::Formatter<'_>,
+    _indent: Option<usize>,
+) -> fmt::Result {
+    Ok(())
+}
+
+/// Finds the most recent attachment of the given type in a chain.
+#[cfg(feature = "alloc")]
+pub(crate) fn request_ref<'err, A: 'static>(chain: Chain<'err>) -> Option<&'err A> {
+    chain
+        .filter_map(|error| error.downcast_ref::<Attached>())
+        .find_map(Attached::request_ref)
+}
@@ -0,0 +1,139 @@
+//! # Ser
+//!
+//! Helpers for the `Serialize` implementations made by
+//! `#[error(serialize)]`.
+//!
+//! These aren't meant to be used directly. They're public so that the derive
+//! can reach them through `pisserror::__private`.
+
+use core::fmt::Display;
+
+use serde::ser::{Serialize, SerializeMap as _, SerializeSeq as _, Serializer};
+
+use crate::StdError;
+
+/// Serializes something using its `Display` message.
+#[derive(Debug)]
+pub struct Message<'err, T: ?Sized>(pub &'err T);
+
+impl<T: Display + ?Sized> Serialize for Message<'_, T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self.0)
+    }
+}
+
+/// Serializes a collection of errors as a list of their messages.
+#[derive(Debug)]
+pub struct Messages<'err, T: ?Sized>(pub &'err T);
+
+impl<'err, T> Serialize for Messages<'err, T>
+where
+    T: ?Sized,
+    &'err T: IntoIterator,
+    <&'err T as IntoIterator>::Item: Display,
+{
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for error in self.0 {
+            seq.serialize_element(&Message(&error))?;
+        }
+        seq.end()
+    }
+}
+
+/// Serializes an error's chain of sources as nested `message`/`source`
+/// objects, or `null` if there isn't one.
+#[derive(Debug)]
+pub struct Source<'err>(pub Option<&'err (dyn StdError + 'static)>);
+
+impl Serialize for Source<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(error) = self.0 else {
+            return serializer.serialize_none();
+        };
+
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("message", &Message(error))?;
+        map.serialize_entry("source", &Self(error.source()))?;
+        map.end()
+    }
+}
+
+/// Serializes a variant's fields as a map.
+#[derive(Debug)]
+pub struct Fields<L>(pub L);
+
+impl<L: FieldList> Serialize for Fields<L> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        self.0.serialize_entries(&mut map)?;
+        map.end()
+    }
+}
+
+/// One field in a list of fields.
+///
+/// These chain together like a linked list, ending with `()`. That way, each
+/// field keeps its own type, and nothing needs to be boxed.
+#[derive(Debug)]
+pub struct Field<'err, V: ?Sized, Next> {
+    name: &'static str,
+    value: &'err V,
+    next: Next,
+}
+
+impl<'err, V: ?Sized, Next> Field<'err, V, Next> {
+    /// Puts a field in front of the rest of the list.
+    #[inline]
+    pub const fn new(name: &'static str, value: &'err V, next: Next) -> Self {
+        Self { name, value, next }
+    }
+}
+
+/// A list of fields that can be written into a map.
+pub trait FieldList {
+    /// The number of fields in the list.
+    fn len(&self) -> usize;
+
+    /// Checks if the list has no fields.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes each field into the map.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any field fails to serialize.
+    fn serialize_entries<M: serde::ser::SerializeMap>(&self, map: &mut M) -> Result<(), M::Error>;
+}
+
+impl FieldList for () {
+    #[inline]
+    fn len(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn serialize_entries<M: serde::ser::SerializeMap>(&self, _map: &mut M) -> Result<(), M::Error> {
+        Ok(())
+    }
+}
+
+impl<V: Serialize + ?Sized, Next: FieldList> FieldList for Field<'_, V, Next> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.next.len().saturating_add(1)
+    }
+
+    #[inline]
+    fn serialize_entries<M: serde::ser::SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry(self.name, self.value)?;
+        self.next.serialize_entries(map)
+    }
+}
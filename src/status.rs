@@ -0,0 +1,75 @@
+//! # Status
+//!
+//! Mapping errors to HTTP status codes.
+
+/// An error that knows which HTTP status code it should be reported with.
+///
+/// `#[derive(Error)]` implements this when the enum or any of its variants
+/// has a `status` option, or when it has a `transparent` variant:
+///
+/// ```
+/// use pisserror::{Error, HttpStatus};
+/// use std::error::Error;
+///
+/// #[derive(Debug, Error)]
+/// #[error(status = 500)]
+/// enum ApiError {
+///     #[error("no user named `{_0}`")]
+///     #[error(status = 404)]
+///     NoUser(String),
+///     #[error("the database is down")]
+///     Database(#[from] std::io::Error),
+/// }
+///
+/// assert_eq!(ApiError::NoUser(String::from("barrett")).status_code(), 404);
+///
+/// // variants without a `status` use the enum's
+/// let err = ApiError::from(std::io::Error::other("connection refused"));
+/// assert_eq!(err.status_code(), 500);
+/// ```
+///
+/// Without an enum-level `status`, variants without one use
+/// `500 Internal Server Error`. `transparent` variants without a `status`
+/// ask their inner error, if it also implements `HttpStatus`.
+pub trait HttpStatus {
+    /// The HTTP status code for this error.
+    fn status_code(&self) -> u16;
+}
+
+/// Wraps an inner error so the derive can check if it implements
+/// [`HttpStatus`].
+///
+/// Calling `(&StatusOf(e)).status_code_or(default)` uses `HttpStatus` when
+/// `e` implements it. Otherwise, method resolution falls through to
+/// [`StatusFallback`], which returns the default.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct StatusOf<'err, T: ?Sized>(pub &'err T);
+
+/// Picked when the inner error implements [`HttpStatus`].
+#[doc(hidden)]
+pub trait StatusViaTrait {
+    /// Asks the inner error for its status code.
+    fn status_code_or(&self, default: u16) -> u16;
+}
+
+impl<T: HttpStatus + ?Sized> StatusViaTrait for StatusOf<'_, T> {
+    #[inline]
+    fn status_code_or(&self, _default: u16) -> u16 {
+        self.0.status_code()
+    }
+}
+
+/// Picked when the inner error doesn't implement [`HttpStatus`].
+#[doc(hidden)]
+pub trait StatusFallback {
+    /// Returns the default status code.
+    fn status_code_or(&self, default: u16) -> u16;
+}
+
+impl<T: ?Sized> StatusFallback for &StatusOf<'_, T> {
+    #[inline]
+    fn status_code_or(&self, default: u16) -> u16 {
+        default
+    }
+}
@@ -0,0 +1,45 @@
+//! # Trace
+//!
+//! Recording errors as structured `tracing` events.
+
+use tracing::Level;
+
+/// An error that can record itself as a `tracing` event.
+///
+/// With `#[error(trace)]` on the enum, `#[derive(Error)]` implements this,
+/// along with [`Severity`](crate::Severity). Each event has the variant's
+/// name as `error.kind`, its message as `error.message`, and its `errno`
+/// code (if any) as `error.code`. Every field is recorded with its `Debug`
+/// form, unless it's marked with `#[error(skip_serializing)]`:
+///
+/// ```
+/// use pisserror::{Error, ResultExt as _, Trace};
+/// use std::error::Error;
+///
+/// #[derive(Debug, Error)]
+/// #[error(trace)]
+/// enum LoginError {
+///     #[error("no account named `{user}`")]
+///     NoAccount { user: String },
+///     #[error("wrong password for `{user}`")]
+///     WrongPassword {
+///         user: String,
+///         #[error(skip_serializing)]
+///         password: String,
+///     },
+/// }
+///
+/// fn log_in(user: &str) -> Result<(), LoginError> {
+///     Err(LoginError::NoAccount { user: user.into() })
+/// }
+///
+/// // records `error.kind = "NoAccount"`, `error.message`, and `user`
+/// LoginError::NoAccount { user: "barrett".into() }.trace(tracing::Level::WARN);
+///
+/// // or, at the error's `level` (`ERROR` by default), on the way up
+/// assert!(log_in("barrett").trace_err().is_err());
+/// ```
+pub trait Trace {
+    /// Records this error as an event at the given level.
+    fn trace(&self, level: Level);
+}
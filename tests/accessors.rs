@@ -0,0 +1,50 @@
+//! Checks the methods made by `#[error(accessors)]`.
+
+#[cfg(test)]
+mod tests {
+    use pisserror::Error;
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    #[error(accessors)]
+    enum DbError {
+        #[error("no value for the key `{key}`")]
+        KeyNotFound { key: String },
+        #[error("row {_0} has {_1} columns")]
+        BadRow(usize, usize),
+        #[error(transparent)]
+        Format(#[from] core::fmt::Error),
+        #[error("the table is locked")]
+        Locked,
+    }
+
+    #[test]
+    fn predicates_check_the_variant() {
+        let locked = DbError::Locked;
+        assert!(locked.is_locked());
+        assert!(!locked.is_key_not_found());
+        assert!(!locked.is_bad_row());
+        assert!(!locked.is_format());
+    }
+
+    #[test]
+    fn accessors_borrow_and_take_fields() {
+        let missing = DbError::KeyNotFound {
+            key: String::from("piss"),
+        };
+        assert_eq!(missing.as_key_not_found().map(String::as_str), Some("piss"));
+        assert_eq!(missing.as_bad_row(), None);
+        assert_eq!(missing.into_key_not_found().unwrap(), "piss");
+
+        let row = DbError::BadRow(4, 2);
+        assert_eq!(row.as_bad_row(), Some((&4, &2)));
+        assert_eq!(row.into_bad_row().unwrap(), (4, 2));
+
+        // other variants come back out of `into_*`
+        let format = DbError::from(core::fmt::Error)
+            .into_key_not_found()
+            .unwrap_err();
+        assert!(format.as_format().is_some());
+        assert!(format.source().is_some());
+    }
+}
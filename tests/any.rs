@@ -0,0 +1,72 @@
+//! Checks that `AnyError` and `Report` render and downcast as expected.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+#[allow(clippy::use_debug)]
+mod tests {
+    use pisserror::{AnyError, Error, Report, WrapErr as _};
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    enum DatabaseError {
+        #[error("the disk is on fire")]
+        Disk(#[from] std::io::Error),
+        #[error("key `{_0}` not found")]
+        KeyNotFound(String),
+    }
+
+    fn disk_error() -> DatabaseError {
+        std::io::Error::new(std::io::ErrorKind::Other, "smoke detected").into()
+    }
+
+    #[test]
+    fn report_renders_chain() {
+        let report = Report::new(disk_error());
+
+        assert_eq!(report.to_string(), "the disk is on fire: smoke detected");
+        assert_eq!(
+            format!("{report:?}"),
+            "the disk is on fire\n\nCaused by:\n      smoke detected"
+        );
+        assert_eq!(report.chain().count(), 2);
+    }
+
+    #[test]
+    fn report_numbers_many_causes() {
+        let err = AnyError::new(disk_error()).context("failed to save");
+
+        assert_eq!(
+            format!("{err:?}"),
+            "failed to save\n\nCaused by:\n      0: the disk is on fire\n      1: smoke detected"
+        );
+        assert_eq!(
+            Report::new(err).pretty(true).to_string(),
+            "failed to save\n\nCaused by:\n      0: the disk is on fire\n      1: smoke detected"
+        );
+    }
+
+    #[test]
+    fn any_error_downcasts() {
+        let err = AnyError::from(DatabaseError::KeyNotFound(String::from("piss")));
+        assert!(err.is::<DatabaseError>());
+        assert!(!err.is::<std::io::Error>());
+
+        let Ok(DatabaseError::KeyNotFound(key)) = err.downcast::<DatabaseError>() else {
+            panic!("the error should downcast to its original type");
+        };
+        assert_eq!(key, "piss");
+    }
+
+    #[test]
+    fn wrap_err_keeps_sources() {
+        let result: Result<(), DatabaseError> = Err(disk_error());
+        let err = result.wrap_err_with(|| "failed to save").unwrap_err();
+
+        assert_eq!(err.to_string(), "failed to save");
+        assert!(err.downcast_ref::<DatabaseError>().is_none());
+        assert!(err.chain().nth(1).unwrap().is::<DatabaseError>());
+        assert!(err.root_cause().is::<std::io::Error>());
+        assert!(err.source().is_some());
+    }
+}
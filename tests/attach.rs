@@ -0,0 +1,77 @@
+//! Checks that attachments survive being passed through derived errors.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+#[allow(clippy::use_debug)]
+mod tests {
+    use pisserror::{AnyError, Attach as _, Attached, Error, Report, ResultExt as _};
+    use std::error::Error;
+
+    #[derive(Debug, PartialEq)]
+    struct RequestId(u32);
+
+    #[derive(Debug, Error)]
+    enum SyncError {
+        #[error("failed to sync")]
+        Io(#[from] Attached),
+    }
+
+    fn connection_reset() -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset")
+    }
+
+    #[test]
+    fn attachments_compose_with_derived_errors() {
+        let result: Result<(), _> = Err(connection_reset());
+        let err = SyncError::from(
+            result
+                .attach(RequestId(42))
+                .attach_printable("while syncing shard 3")
+                .unwrap_err(),
+        );
+
+        let report = Report::new(err);
+        assert_eq!(report.request_ref::<RequestId>(), Some(&RequestId(42)));
+        assert!(report.request_ref::<String>().is_none());
+        assert_eq!(
+            report.to_string(),
+            "failed to sync: connection reset (while syncing shard 3)"
+        );
+        assert_eq!(
+            format!("{report:?}"),
+            "failed to sync\n\nCaused by:\n      connection reset\n      - while syncing shard 3"
+        );
+    }
+
+    #[test]
+    fn attaching_twice_doesnt_nest() {
+        let err = connection_reset()
+            .attach_printable("first")
+            .attach(RequestId(1))
+            .attach(RequestId(2))
+            .attach_printable("second");
+
+        assert!(err.downcast_ref::<std::io::Error>().is_some());
+        assert_eq!(err.request_ref::<RequestId>(), Some(&RequestId(2)));
+        assert_eq!(
+            Report::new(err).to_string(),
+            "connection reset (first, second)"
+        );
+    }
+
+    #[test]
+    fn any_error_attachments() {
+        let err = AnyError::new(connection_reset())
+            .attach_printable("while syncing shard 3")
+            .context("failed to sync")
+            .attach(RequestId(7));
+
+        assert_eq!(err.request_ref::<RequestId>(), Some(&RequestId(7)));
+        assert_eq!(
+            format!("{err:?}"),
+            "failed to sync\n\nCaused by:\n      connection reset\n      - while syncing shard 3"
+        );
+        assert!(err.source().is_some());
+    }
+}
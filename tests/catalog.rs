@@ -0,0 +1,120 @@
+//! Checks the `ERROR_CATALOG` lists made by `#[error(catalog)]`.
+
+#![cfg(all(feature = "catalog", feature = "alloc"))]
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+    use pisserror::{catalog_markdown, Error};
+    use std::error::Error;
+
+    /// Errors from the key-value store.
+    #[derive(Debug, Error)]
+    #[error(catalog, status = 500, exit_code = 1)]
+    enum DbError {
+        /// The key isn't in the table.
+        ///
+        /// Keys are case-sensitive.
+        #[error("no value for `{key}` in {table}")]
+        #[error(status = 404, exit_code = 2)]
+        KeyNotFound { table: String, key: String },
+        /// The table is locked | try again later.
+        #[error("table {_0} is locked")]
+        Locked(u32, Option<&'static str>),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+
+    #[derive(Debug, Error)]
+    #[error(catalog, repr = i32)]
+    enum FfiError {
+        #[error("an argument was invalid")]
+        #[error(errno = -22)]
+        InvalidArgument,
+    }
+
+    #[test]
+    fn each_variant_is_described() {
+        let catalog = DbError::ERROR_CATALOG;
+        assert_eq!(catalog.len(), 3);
+
+        let not_found = &catalog[0];
+        assert_eq!(not_found.name(), "KeyNotFound");
+        assert_eq!(not_found.code(), None);
+        assert_eq!(not_found.message(), Some("no value for `{key}` in {table}"));
+        assert_eq!(
+            not_found.doc(),
+            "The key isn't in the table.\n\nKeys are case-sensitive."
+        );
+        assert_eq!(not_found.status(), Some(404));
+        assert_eq!(not_found.exit_code(), Some(2));
+
+        let fields = not_found
+            .fields()
+            .iter()
+            .map(|field| (field.name(), field.ty()))
+            .collect::<Vec<_>>();
+        assert_eq!(fields, [("table", "String"), ("key", "String")]);
+
+        let locked = &catalog[1];
+        assert_eq!(locked.fields()[1].name(), "1");
+        assert_eq!(locked.fields()[1].ty(), "Option<&'static str>");
+
+        // enum defaults fill in, and transparent variants have no message
+        let io = &catalog[2];
+        assert_eq!(io.message(), None);
+        assert_eq!(io.doc(), "");
+        assert_eq!((io.status(), io.exit_code()), (Some(500), Some(1)));
+        assert!(DbError::from(std::io::Error::other("oh no"))
+            .source()
+            .is_some());
+
+        assert_eq!(FfiError::ERROR_CATALOG[0].code(), Some(-22));
+    }
+
+    #[test]
+    fn catalogs_become_markdown() {
+        // the catalog is only for docs, so messages still work as usual
+        let err = DbError::KeyNotFound {
+            table: "users".into(),
+            key: "barrett".into(),
+        };
+        assert_eq!(err.to_string(), "no value for `barrett` in users");
+        assert_eq!(DbError::Locked(7, None).to_string(), "table 7 is locked");
+
+        let markdown = catalog_markdown("DbError", DbError::ERROR_CATALOG);
+        let lines = markdown.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], "## `DbError`");
+        assert_eq!(
+            lines[4],
+            "| `KeyNotFound` |  | 404 | 2 | no value for `{key}` in {table} | `table: String`, \
+            `key: String` | The key isn't in the table.<br><br>Keys are case-sensitive. |"
+        );
+        assert_eq!(
+            lines[5],
+            "| `Locked` |  | 500 | 1 | table {_0} is locked | `0: u32`, \
+            `1: Option<&'static str>` | The table is locked \\| try again later. |"
+        );
+        assert_eq!(lines.len(), 7);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn catalogs_become_json() {
+        let json = serde_json::to_value(FfiError::ERROR_CATALOG).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "name": "InvalidArgument",
+                "code": -22,
+                "message": "an argument was invalid",
+                "fields": [],
+                "doc": "",
+                "status": null,
+                "exit_code": null,
+            }])
+        );
+    }
+}
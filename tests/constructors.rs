@@ -0,0 +1,39 @@
+//! Checks the functions made by `#[error(constructors)]`.
+
+#[cfg(test)]
+mod tests {
+    use pisserror::Error;
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    #[error(constructors)]
+    enum RequestError {
+        #[error("expected a `{expected}` header, got `{got}`")]
+        MalformedHeader { expected: String, got: String },
+        #[error("the request took {_0} seconds")]
+        Timeout(u64),
+        #[error(transparent)]
+        Format(#[from] core::fmt::Error),
+        #[error("the request was cancelled")]
+        Cancelled,
+    }
+
+    #[test]
+    fn constructors_convert_their_arguments() {
+        let header = RequestError::malformed_header("Host", String::from("Hots"));
+        assert_eq!(header.to_string(), "expected a `Host` header, got `Hots`");
+
+        let timeout = RequestError::timeout(30_u8);
+        assert!(matches!(timeout, RequestError::Timeout(30)));
+
+        let cancelled = RequestError::cancelled();
+        assert_eq!(cancelled.to_string(), "the request was cancelled");
+    }
+
+    #[test]
+    fn from_variants_get_an_alias() {
+        let format = RequestError::format(core::fmt::Error);
+        assert!(matches!(format, RequestError::Format(_)));
+        assert!(format.source().is_some());
+    }
+}
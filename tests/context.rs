@@ -0,0 +1,101 @@
+//! Checks the context selectors made by `#[error(context_selectors)]`.
+
+#![cfg(feature = "std")]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use core::num::ParseFloatError;
+    use pisserror::{ContextExt as _, Error, IntoError as _};
+    use std::{error::Error, io, path::PathBuf};
+
+    #[derive(Debug, Error)]
+    #[error(context_selectors)]
+    enum ConfigError {
+        #[error("couldn't read `{}`", path.display())]
+        Read {
+            path: PathBuf,
+            #[source]
+            source: io::Error,
+        },
+        #[error("line {line} of `{file}` is bad")]
+        BadLine { file: String, line: u32 },
+        #[error("couldn't parse `{_0}`")]
+        Parse(String, #[source] core::num::ParseIntError),
+        #[error(transparent)]
+        Disk(#[from] io::Error),
+        #[error("couldn't fetch `{url}`")]
+        Fetch {
+            url: String,
+            #[source]
+            last_error: Option<io::Error>,
+        },
+        #[error("couldn't parse a float")]
+        Float(#[from(via = Arc::new)] Arc<ParseFloatError>),
+        #[error("the config is empty")]
+        Empty,
+    }
+
+    fn read(path: &str) -> Result<String, ConfigError> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "gone")).context(ReadSnafu { path })
+    }
+
+    #[test]
+    fn context_fills_in_the_source() {
+        let err = read("/etc/piss.toml").unwrap_err();
+        assert!(
+            matches!(err, ConfigError::Read { ref path, .. } if path == &PathBuf::from("/etc/piss.toml"))
+        );
+        assert_eq!(err.to_string(), "couldn't read `/etc/piss.toml`");
+        assert_eq!(err.source().unwrap().to_string(), "gone");
+
+        let parse = "twelve"
+            .parse::<u8>()
+            .context(ParseSnafu("twelve"))
+            .unwrap_err();
+        assert!(matches!(parse, ConfigError::Parse(ref text, _) if text == "twelve"));
+        assert!(parse.source().is_some());
+
+        // `#[from]` variants have nothing else to fill in
+        let disk = DiskSnafu.into_error(io::Error::new(io::ErrorKind::Other, "full"));
+        assert!(matches!(disk, ConfigError::Disk(_)));
+    }
+
+    #[test]
+    fn context_wraps_the_source() {
+        // optional sources don't need a `Some`...
+        let fetch = Err::<(), _>(io::Error::new(io::ErrorKind::TimedOut, "slow"))
+            .context(FetchSnafu {
+                url: "https://example.com",
+            })
+            .unwrap_err();
+        assert!(matches!(
+            fetch,
+            ConfigError::Fetch {
+                last_error: Some(_),
+                ..
+            }
+        ));
+        assert_eq!(fetch.source().unwrap().to_string(), "slow");
+
+        // ...and `via` sources don't need converting
+        let float = "piss".parse::<f32>().context(FloatSnafu).unwrap_err();
+        assert!(float.source().unwrap().is::<ParseFloatError>());
+    }
+
+    #[test]
+    fn selectors_without_sources_build() {
+        let err = BadLineSnafu {
+            file: "piss.toml",
+            line: 3_u32,
+        }
+        .build();
+        assert_eq!(err.to_string(), "line 3 of `piss.toml` is bad");
+        assert!(err.source().is_none());
+
+        let empty: Result<(), ConfigError> = EmptySnafu.fail();
+        assert!(matches!(empty, Err(ConfigError::Empty)));
+    }
+}
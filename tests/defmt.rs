@@ -0,0 +1,55 @@
+//! Checks the `defmt::Format` implementations made by `#[error(defmt)]`.
+//!
+//! Actually formatting needs a `defmt` logger, which tests on the host don't
+//! have, so these only check that each error can be formatted.
+
+#![cfg(feature = "defmt")]
+
+#[cfg(test)]
+mod tests {
+    use pisserror::Error;
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    #[error(defmt)]
+    enum SensorError {
+        #[error("sensor {id} timed out after {_ms}ms")]
+        Timeout { id: u8, _ms: u32 },
+        #[error("bad register {{{:#x}}}: {:?}", register, value)]
+        BadRegister { register: u8, value: u16 },
+        #[error("the bus is busy")]
+        Busy,
+    }
+
+    #[derive(Debug, Error)]
+    #[error(defmt)]
+    enum BoardError {
+        #[error(transparent)]
+        Sensor(#[from] SensorError),
+        #[error("{}", "many sensors failed")]
+        Unknown,
+        #[error(many)]
+        Many(Vec<SensorError>),
+    }
+
+    /// Only compiles if the error can be formatted.
+    const fn assert_format<T: defmt::Format>(_: &T) {}
+
+    #[test]
+    fn errors_implement_format() {
+        let timeout = SensorError::Timeout { id: 3, _ms: 250 };
+        assert_format(&timeout);
+        assert_format(&SensorError::Busy);
+        assert_format(&BoardError::Unknown);
+        assert_format(&BoardError::Many(vec![timeout]));
+
+        // `Display` still works as usual
+        let err = BoardError::from(SensorError::BadRegister {
+            register: 16,
+            value: 2,
+        });
+        assert_format(&err);
+        assert!(err.source().is_some());
+        assert_eq!(err.to_string(), "bad register {0x10}: 2");
+    }
+}
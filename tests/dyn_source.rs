@@ -0,0 +1,58 @@
+//! Checks that sources held as trait objects, in a `Box`, `Arc`, or `Rc`,
+//! are returned by `source()`.
+
+#![cfg(feature = "std")]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, rc::Rc, sync::Arc};
+    use pisserror::Error;
+    use std::{error::Error, io};
+
+    #[derive(Debug, Error)]
+    enum PluginError {
+        #[error("the plugin failed")]
+        Boxed(#[from] Box<dyn Error + Send + Sync>),
+        #[error("a shared plugin failed")]
+        Shared {
+            #[from]
+            inner: Arc<dyn Error + Send + Sync>,
+        },
+        #[error("plugin `{name}` failed")]
+        Local {
+            name: String,
+            #[source]
+            cause: Rc<dyn Error>,
+        },
+    }
+
+    fn not_found() -> io::Error {
+        io::Error::new(io::ErrorKind::NotFound, "no such plugin")
+    }
+
+    #[test]
+    fn boxed_sources() {
+        let err = PluginError::from(Box::<dyn Error + Send + Sync>::from(not_found()));
+        assert!(err.source().unwrap().is::<io::Error>());
+    }
+
+    #[test]
+    fn arc_sources() {
+        let shared: Arc<dyn Error + Send + Sync> = Arc::new(not_found());
+        let err = PluginError::from(Arc::clone(&shared));
+        assert!(err.source().unwrap().is::<io::Error>());
+        assert_eq!(Arc::strong_count(&shared), 2);
+    }
+
+    #[test]
+    fn rc_sources() {
+        let err = PluginError::Local {
+            name: String::from("piss"),
+            cause: Rc::new(not_found()),
+        };
+        assert_eq!(err.to_string(), "plugin `piss` failed");
+        assert_eq!(err.source().unwrap().to_string(), "no such plugin");
+    }
+}
@@ -0,0 +1,63 @@
+//! Checks the `ProcessExit` implementations made by the `exit_code` option,
+//! along with `Exit`.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+mod tests {
+    use pisserror::{Error, Exit, ProcessExit};
+    use std::{
+        error::Error,
+        process::{ExitCode, Termination as _},
+    };
+
+    #[derive(Debug, Error)]
+    #[error(exit_code = 70)]
+    enum CliError {
+        #[error("couldn't read the input")]
+        #[error(exit_code = 66)]
+        NoInput(#[from] std::io::Error),
+        #[error("bad flag: `{_0}`")]
+        #[error(exit_code = 64)]
+        Usage(String),
+        #[error("something went wrong")]
+        Internal,
+    }
+
+    #[derive(Debug, Error)]
+    enum NoDefault {
+        #[error("the config is malformed")]
+        #[error(exit_code = 78)]
+        Config,
+        #[error("oops")]
+        Oops,
+    }
+
+    #[test]
+    fn variant_exit_codes() {
+        let err = CliError::from(std::io::Error::other("permission denied"));
+        assert!(err.source().is_some());
+        assert_eq!(err.exit_code(), 66);
+        assert_eq!(CliError::Usage(String::from("--piss")).exit_code(), 64);
+        assert_eq!(NoDefault::Config.exit_code(), 78);
+    }
+
+    #[test]
+    fn default_exit_codes() {
+        assert_eq!(CliError::Internal.exit_code(), 70);
+        assert_eq!(NoDefault::Oops.exit_code(), 1);
+    }
+
+    #[test]
+    fn exit_terminates_with_the_exit_code() {
+        assert_eq!(Exit::<CliError>(Ok(())).report(), ExitCode::SUCCESS);
+        assert_eq!(
+            Exit::from(CliError::Usage(String::from("--piss"))).report(),
+            ExitCode::from(64)
+        );
+        assert_eq!(
+            Exit::<NoDefault>::from(Err(NoDefault::Oops)).report(),
+            ExitCode::from(1)
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Checks `#[from(...)]` lists, which make one `From` for each listed type,
+//! and `via` conversions.
+
+#![cfg(feature = "std")]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{rc::Rc, sync::Arc};
+    use core::num::{ParseFloatError, ParseIntError};
+    use pisserror::Error;
+    use std::{error::Error, io};
+
+    #[derive(Debug, Error)]
+    enum NumberError {
+        #[error("couldn't parse the number: {_0}")]
+        Parse(#[from(ParseIntError, ParseFloatError)] Box<dyn Error + Send + Sync>),
+    }
+
+    fn parse(int: &str, float: &str) -> Result<f64, NumberError> {
+        let whole: i32 = int.parse()?;
+        let fraction: f64 = float.parse()?;
+        Ok(f64::from(whole) + fraction)
+    }
+
+    #[test]
+    fn each_listed_type_converts() {
+        assert!(parse("1", "0.5").is_ok());
+
+        let int = parse("one", "0.5").unwrap_err();
+        assert!(int.source().unwrap().is::<ParseIntError>());
+
+        let float = parse("1", "half").unwrap_err();
+        assert!(float.source().unwrap().is::<ParseFloatError>());
+        assert_eq!(
+            float.to_string(),
+            "couldn't parse the number: invalid float literal"
+        );
+    }
+
+    #[derive(Clone, Debug, Error)]
+    enum SharedError {
+        #[error("couldn't read the file")]
+        Io(#[from(via = Arc::new)] Arc<io::Error>),
+        #[error("couldn't write the message")]
+        Fmt(#[from(core::fmt::Error)] Rc<core::fmt::Error>),
+    }
+
+    #[test]
+    fn via_wraps_the_source() {
+        let err = SharedError::from(io::Error::new(io::ErrorKind::NotFound, "gone"));
+        let cloned = err.clone();
+        assert!(matches!(err, SharedError::Io(ref io) if Arc::strong_count(io) == 2));
+
+        // `source` looks through the `Arc`, so the error can be downcast
+        let source = cloned.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<io::Error>().unwrap().kind(),
+            io::ErrorKind::NotFound
+        );
+
+        let fmt = SharedError::from(core::fmt::Error);
+        assert!(fmt.source().unwrap().is::<core::fmt::Error>());
+    }
+}
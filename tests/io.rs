@@ -0,0 +1,94 @@
+//! Checks the conversions into `std::io::Error` made by the `io_kind` option.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+mod tests {
+    use pisserror::Error;
+    use std::{
+        error::Error,
+        io::{self, ErrorKind},
+    };
+
+    #[derive(Debug, Error)]
+    enum StoreError {
+        #[error("no such key: `{_0}`")]
+        #[error(io_kind = NotFound)]
+        NoKey(String),
+        #[error("the store is read-only")]
+        #[error(io_kind = PermissionDenied)]
+        ReadOnly,
+        #[error(transparent)]
+        Io(#[from] io::Error),
+        #[error("the store is corrupted")]
+        Corrupted,
+    }
+
+    #[derive(Debug, Error)]
+    #[error(io_kind = InvalidData)]
+    enum DecodeError {
+        #[error("bad magic number")]
+        BadMagic,
+        #[error("the stream ended early")]
+        #[error(io_kind = UnexpectedEof)]
+        Truncated,
+        #[error("couldn't read the stream")]
+        Read(#[from] io::Error),
+    }
+
+    #[test]
+    fn kinds_come_from_variants() {
+        let io = io::Error::from(StoreError::NoKey(String::from("piss")));
+        assert_eq!(io.kind(), ErrorKind::NotFound);
+        assert_eq!(io.to_string(), "no such key: `piss`");
+
+        let io = io::Error::from(StoreError::ReadOnly);
+        assert_eq!(io.kind(), ErrorKind::PermissionDenied);
+
+        // no `io_kind` and no enum default
+        let io = io::Error::from(StoreError::Corrupted);
+        assert_eq!(io.kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn enum_default_kind() {
+        assert_eq!(
+            io::Error::from(DecodeError::BadMagic).kind(),
+            ErrorKind::InvalidData
+        );
+        assert_eq!(
+            io::Error::from(DecodeError::Truncated).kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn original_error_is_retrievable() {
+        let io = io::Error::from(StoreError::NoKey(String::from("piss")));
+        let store = io.get_ref().unwrap().downcast_ref::<StoreError>().unwrap();
+        assert!(matches!(*store, StoreError::NoKey(ref key) if key == "piss"));
+
+        let inner = io.into_inner().unwrap().downcast::<StoreError>().unwrap();
+        assert!(matches!(*inner, StoreError::NoKey(_)));
+    }
+
+    #[test]
+    fn transparent_io_errors_are_unwrapped() {
+        let original = io::Error::new(ErrorKind::TimedOut, "the disk is asleep");
+        let io = io::Error::from(StoreError::from(original));
+
+        assert_eq!(io.kind(), ErrorKind::TimedOut);
+        assert_eq!(io.to_string(), "the disk is asleep");
+        assert!(io.get_ref().unwrap().downcast_ref::<StoreError>().is_none());
+    }
+
+    #[test]
+    fn non_transparent_io_errors_are_kept() {
+        let original = io::Error::new(ErrorKind::TimedOut, "the disk is asleep");
+        let io = io::Error::from(DecodeError::from(original));
+
+        assert_eq!(io.kind(), ErrorKind::InvalidData);
+        let decode = io.get_ref().unwrap().downcast_ref::<DecodeError>().unwrap();
+        assert_eq!(decode.source().unwrap().to_string(), "the disk is asleep");
+    }
+}
@@ -0,0 +1,111 @@
+//! Checks the `localize` methods made by the `l10n` option.
+
+#![cfg(feature = "i18n")]
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Display;
+    use pisserror::{Error, MessageSource};
+    use std::{collections::HashMap, error::Error};
+
+    #[derive(Debug, Error)]
+    enum DbError {
+        #[error("no value for the key `{key}` in `{table}`")]
+        #[error(l10n = "db-key-not-found")]
+        KeyNotFound { table: String, key: String },
+        #[error("table {_0} is locked")]
+        #[error(l10n = "db-locked")]
+        Locked(u32),
+        #[error("wrong password for `{user}`")]
+        #[error(l10n = "db-wrong-password")]
+        WrongPassword {
+            user: String,
+            #[error(skip_l10n)]
+            password: String,
+            #[error(skip_l10n)]
+            attempts: Vec<u32>,
+        },
+        #[error("the disk is full")]
+        DiskFull,
+    }
+
+    /// A Fluent-ish catalog, where `{ $name }` is replaced by an argument.
+    struct Catalog(HashMap<&'static str, &'static str>);
+
+    impl MessageSource for Catalog {
+        fn message(&self, id: &str, args: &[(&str, &dyn Display)]) -> Option<String> {
+            let mut message = (*self.0.get(id)?).to_owned();
+            for &(name, value) in args {
+                message = message.replace(&format!("{{ ${name} }}"), &value.to_string());
+            }
+            Some(message)
+        }
+    }
+
+    fn german() -> Catalog {
+        Catalog(HashMap::from([
+            (
+                "db-key-not-found",
+                "kein Wert für `{ $key }` in `{ $table }`",
+            ),
+            ("db-locked", "Tabelle { $_0 } ist gesperrt"),
+            (
+                "db-wrong-password",
+                "falsches Passwort für `{ $user }`{ $password }",
+            ),
+        ]))
+    }
+
+    #[test]
+    fn fields_are_named_arguments() {
+        let err = DbError::KeyNotFound {
+            table: "users".into(),
+            key: "barrett".into(),
+        };
+        assert_eq!(
+            err.localize(&german()),
+            "kein Wert für `barrett` in `users`"
+        );
+        assert_eq!(
+            DbError::Locked(7).localize(&german()),
+            "Tabelle 7 ist gesperrt"
+        );
+    }
+
+    #[test]
+    fn skipped_fields_are_left_out() {
+        let err = DbError::WrongPassword {
+            user: "barrett".into(),
+            password: "hunter2".into(),
+            attempts: vec![1, 2, 3],
+        };
+        assert_eq!(
+            err.localize(&german()),
+            "falsches Passwort für `barrett`{ $password }"
+        );
+    }
+
+    #[test]
+    fn english_is_the_fallback() {
+        // no `l10n` option
+        assert_eq!(DbError::DiskFull.localize(&german()), "the disk is full");
+
+        // not in the catalog
+        let empty = Catalog(HashMap::new());
+        assert_eq!(DbError::Locked(7).localize(&empty), "table 7 is locked");
+        assert!(DbError::Locked(7).source().is_none());
+    }
+
+    #[test]
+    fn message_ids_cover_the_catalog() {
+        assert_eq!(
+            DbError::MESSAGE_IDS,
+            ["db-key-not-found", "db-locked", "db-wrong-password"]
+        );
+
+        let catalog = german();
+        assert!(DbError::MESSAGE_IDS
+            .iter()
+            .all(|id| catalog.0.contains_key(id)));
+    }
+}
@@ -0,0 +1,56 @@
+//! Checks the `Severity` implementations made by the `level` option.
+
+#[cfg(test)]
+mod tests {
+    use pisserror::{Error, Level, Severity};
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    enum CacheError {
+        #[error("no cached entry for `{key}`")]
+        #[error(level = debug)]
+        Miss { key: String },
+        #[error("the cache is getting full")]
+        #[error(level = warn)]
+        AlmostFull,
+        #[error("the disk is full")]
+        DiskFull,
+    }
+
+    #[derive(Debug, Error)]
+    #[error(level = info)]
+    enum SyncError {
+        #[error("couldn't reach the server")]
+        Offline,
+        #[error("the server is on fire")]
+        #[error(level = error)]
+        OnFire,
+        #[error(transparent)]
+        Cache(#[from] CacheError),
+    }
+
+    #[test]
+    fn variant_levels() {
+        let miss = CacheError::Miss {
+            key: String::from("piss"),
+        };
+        assert!(miss.source().is_none());
+        assert_eq!(miss.level(), Level::Debug);
+        assert_eq!(CacheError::AlmostFull.level(), Level::Warn);
+        assert_eq!(SyncError::OnFire.level(), Level::Error);
+    }
+
+    #[test]
+    fn default_levels() {
+        assert_eq!(CacheError::DiskFull.level(), Level::Error);
+        assert_eq!(SyncError::Offline.level(), Level::Info);
+        assert_eq!(SyncError::from(CacheError::DiskFull).level(), Level::Info);
+    }
+
+    #[test]
+    fn levels_are_ordered_by_severity() {
+        assert!(Level::Trace < Level::Debug);
+        assert!(Level::Warn < Level::Error);
+        assert!(SyncError::Offline.level() < CacheError::DiskFull.level());
+    }
+}
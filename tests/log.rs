@@ -0,0 +1,65 @@
+//! Checks `ResultExt::log_err`, which logs at each error's level.
+
+#![cfg(feature = "log")]
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+    use std::sync::Mutex;
+
+    use log::{Level, Log, Metadata, Record};
+    use pisserror::{Error, ResultExt as _};
+    use std::error::Error;
+
+    /// A logger that holds onto every record it sees.
+    struct Recorder(Mutex<Vec<(Level, String)>>);
+
+    static RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+
+    impl Log for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[derive(Debug, Error)]
+    enum CacheError {
+        #[error("no cached entry for `{_0}`")]
+        #[error(level = debug)]
+        Miss(String),
+        #[error("the disk is full")]
+        DiskFull,
+    }
+
+    #[test]
+    fn log_err_logs_at_each_level() {
+        log::set_logger(&RECORDER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let ok: Result<(), CacheError> = Ok(());
+        assert!(ok.log_err().is_ok());
+
+        let miss: Result<(), CacheError> = Err(CacheError::Miss(String::from("piss")));
+        assert!(matches!(miss.log_err(), Err(CacheError::Miss(_))));
+
+        let full: Result<(), CacheError> = Err(CacheError::DiskFull);
+        assert!(full.log_err().unwrap_err().source().is_none());
+
+        let records = RECORDER.0.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0],
+            (Level::Debug, String::from("no cached entry for `piss`"))
+        );
+        assert_eq!(records[1], (Level::Error, String::from("the disk is full")));
+    }
+}
@@ -0,0 +1,55 @@
+//! Checks that `MultiError` works on its own and inside derived errors.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+mod tests {
+    use core::num::ParseIntError;
+    use pisserror::{Error, MultiError};
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    enum ConfigError {
+        #[error(many)]
+        Invalid(MultiError<ParseIntError>),
+    }
+
+    fn parse_all(ports: &[&str]) -> Result<Vec<u16>, ConfigError> {
+        let mut errors = MultiError::new();
+        let mut parsed = Vec::new();
+
+        for port in ports {
+            match port.parse() {
+                Ok(p) => parsed.push(p),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        errors.into_result().map_err(ConfigError::Invalid)?;
+        Ok(parsed)
+    }
+
+    #[test]
+    fn multi_error_collects_everything() {
+        assert_eq!(parse_all(&["80", "443"]).unwrap(), vec![80, 443]);
+
+        let err = parse_all(&["eighty", "443", "-1"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1. invalid digit found in string\n2. invalid digit found in string"
+        );
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn multi_error_extends() {
+        let mut errors: MultiError<ParseIntError> = MultiError::default();
+        assert!(errors.is_empty());
+        assert_eq!(errors.to_string(), "no errors occurred");
+
+        errors.extend(["a", "b"].iter().filter_map(|s| s.parse::<u8>().err()));
+        assert_eq!(errors.len(), 2);
+        assert_eq!((&errors).into_iter().count(), 2);
+        assert_eq!(errors.into_vec().len(), 2);
+    }
+}
@@ -0,0 +1,44 @@
+//! Checks `#[source]` fields that hold an `Option`.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use pisserror::Error;
+    use std::{error::Error, io};
+
+    #[derive(Debug, Error)]
+    enum RetryError {
+        #[error("timed out after {after:?}")]
+        Timeout {
+            after: Duration,
+            #[source]
+            last_error: Option<io::Error>,
+        },
+        #[error("gave up")]
+        GaveUp(#[source] Option<Box<dyn Error + Send + Sync>>),
+    }
+
+    #[test]
+    fn optional_sources_can_be_missing() {
+        let quiet = RetryError::Timeout {
+            after: Duration::from_secs(5),
+            last_error: None,
+        };
+        assert!(quiet.source().is_none());
+
+        let loud = RetryError::Timeout {
+            after: Duration::from_secs(5),
+            last_error: Some(io::Error::new(io::ErrorKind::TimedOut, "too slow")),
+        };
+        assert_eq!(loud.source().unwrap().to_string(), "too slow");
+    }
+
+    #[test]
+    fn optional_sources_look_through_pointers() {
+        let err = RetryError::GaveUp(Some(Box::new(core::fmt::Error)));
+        assert!(err.source().unwrap().is::<core::fmt::Error>());
+        assert!(RetryError::GaveUp(None).source().is_none());
+    }
+}
@@ -0,0 +1,107 @@
+//! Checks the `to_problem_details` method made by the `status`, `type_uri`,
+//! and `title` options.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+mod tests {
+    use pisserror::{Error, HttpStatus as _, ProblemDetails};
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    enum ApiError {
+        #[error("no user named `{name}`")]
+        #[error(status = 404, type_uri = "https://example.com/probs/no-user")]
+        #[error(title = "No such user")]
+        NoUser { name: String },
+        #[error("the database is down")]
+        #[error(status = 503)]
+        Database(#[from] std::io::Error),
+        #[error("something went wrong")]
+        Internal,
+        #[error(transparent)]
+        Upstream(#[from] UpstreamError),
+    }
+
+    #[derive(Debug, Error)]
+    enum UpstreamError {
+        #[error("the upstream server timed out")]
+        #[error(status = 504)]
+        TimedOut,
+    }
+
+    #[test]
+    fn options_fill_in_members() {
+        let err = ApiError::NoUser {
+            name: String::from("barrett"),
+        };
+
+        assert_eq!(
+            err.to_problem_details().with_instance("/users/barrett"),
+            ProblemDetails {
+                type_uri: String::from("https://example.com/probs/no-user"),
+                title: Some(String::from("No such user")),
+                status: Some(404),
+                detail: Some(String::from("no user named `barrett`")),
+                instance: Some(String::from("/users/barrett")),
+            }
+        );
+    }
+
+    #[test]
+    fn missing_options_use_defaults() {
+        let err = ApiError::from(std::io::Error::other("connection refused"));
+        assert!(err.source().is_some());
+
+        let problem = err.to_problem_details();
+        assert_eq!(problem.type_uri, ProblemDetails::BLANK_TYPE);
+        assert_eq!(problem.status, Some(503));
+        assert_eq!(problem.title, None);
+
+        assert_eq!(
+            ApiError::Internal.to_problem_details(),
+            ProblemDetails::new()
+                .with_status(500)
+                .with_detail("something went wrong")
+        );
+    }
+
+    #[test]
+    fn status_matches_http_status() {
+        // `transparent` variants ask their inner error, just like `HttpStatus`
+        let err = ApiError::from(UpstreamError::TimedOut);
+        assert_eq!(err.to_problem_details().status, Some(504));
+        assert_eq!(err.status_code(), 504);
+
+        assert_eq!(ApiError::Internal.status_code(), 500);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_problem_json() {
+        let problem = ApiError::NoUser {
+            name: String::from("barrett"),
+        }
+        .to_problem_details();
+
+        assert_eq!(
+            serde_json::to_value(&problem).unwrap(),
+            serde_json::json!({
+                "type": "https://example.com/probs/no-user",
+                "title": "No such user",
+                "status": 404,
+                "detail": "no user named `barrett`",
+            })
+        );
+
+        let blank = serde_json::to_value(ApiError::Internal.to_problem_details()).unwrap();
+        assert_eq!(
+            blank,
+            serde_json::json!({
+                "type": "about:blank",
+                "status": 500,
+                "detail": "something went wrong",
+            })
+        );
+    }
+}
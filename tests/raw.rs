@@ -0,0 +1,112 @@
+//! Checks the raw codes made by the `errno` and `repr` options.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+    use core::ffi::{c_char, CStr};
+    use pisserror::{Error, UnknownCode};
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    enum FfiError {
+        #[error("an argument was invalid")]
+        #[error(errno = -22)]
+        InvalidArgument,
+        #[error("no such file: {path}")]
+        #[error(errno = -2)]
+        NotFound { path: String },
+        #[error(transparent)]
+        #[error(errno = -5)]
+        Io(#[from] std::io::Error),
+    }
+
+    #[derive(Debug, Error)]
+    #[error(repr = u8, strerror = small_strerror)]
+    enum SmallError {
+        #[error("first")]
+        #[error(errno = 1)]
+        First,
+        #[error("second")]
+        #[error(errno = 255)]
+        Second,
+    }
+
+    fn message(ptr: *const c_char) -> &'static str {
+        // SAFETY: `strerror` always gives back a `'static` C string.
+        unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()
+    }
+
+    #[test]
+    fn as_raw() {
+        assert_eq!(FfiError::InvalidArgument.as_raw(), -22);
+        assert_eq!(
+            FfiError::NotFound {
+                path: String::from("/piss")
+            }
+            .as_raw(),
+            -2
+        );
+
+        let io = FfiError::from(std::io::Error::other("disk on fire"));
+        assert!(io.source().is_some());
+        assert_eq!(io.as_raw(), -5);
+
+        let code: u8 = SmallError::Second.as_raw();
+        assert_eq!(code, 255);
+    }
+
+    #[test]
+    fn try_from_raw() {
+        assert!(matches!(
+            FfiError::try_from(-22),
+            Ok(FfiError::InvalidArgument)
+        ));
+        assert!(matches!(SmallError::try_from(1), Ok(SmallError::First)));
+
+        // variants with fields can't be made from a code
+        assert_eq!(FfiError::try_from(-2).unwrap_err(), UnknownCode(-2));
+        assert_eq!(SmallError::try_from(7).unwrap_err(), UnknownCode(7));
+        assert_eq!(
+            UnknownCode(7).to_string(),
+            "no unit variant has the code `7`"
+        );
+    }
+
+    #[test]
+    fn raw_code_table() {
+        let table = FfiError::RAW_CODES;
+        assert_eq!(table.len(), 3);
+
+        assert_eq!(table[1].code(), -2);
+        assert_eq!(table[1].name(), "NotFound");
+        assert_eq!(table[1].message().to_str(), Ok("no such file: {path}"));
+        assert_eq!(table[2].message().to_str(), Ok("Io"));
+    }
+
+    #[test]
+    fn strerror() {
+        assert_eq!(
+            message(pisserror::strerror(FfiError::RAW_CODES, -22)),
+            "an argument was invalid"
+        );
+        assert_eq!(
+            message(pisserror::strerror(SmallError::RAW_CODES, 255)),
+            "second"
+        );
+        assert_eq!(
+            message(pisserror::strerror(FfiError::RAW_CODES, 1000)),
+            "unknown error"
+        );
+    }
+
+    #[test]
+    fn extern_strerror() {
+        assert_eq!(message(pisserror_strerror(-2)), "no such file: {path}");
+        assert_eq!(message(pisserror_strerror(1000)), "unknown error");
+
+        // renamed with the `strerror` option
+        assert_eq!(message(small_strerror(1)), "first");
+    }
+}
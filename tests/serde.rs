@@ -0,0 +1,140 @@
+//! Checks the `Serialize` implementations made by `#[error(serialize)]`.
+
+#![cfg(all(feature = "serde", feature = "alloc"))]
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+    use pisserror::{Error, MultiError};
+    use serde_json::json;
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    #[error(serialize)]
+    enum LoginError {
+        #[error("no user named `{user}`")]
+        UnknownUser { user: String, attempts: u32 },
+        #[error("wrong password for `{user}`")]
+        WrongPassword {
+            user: String,
+            #[error(skip_serializing)]
+            password: String,
+        },
+        #[error("the user database couldn't be read")]
+        Database(#[from] std::io::Error),
+        #[error("couldn't sync with `{server}`")]
+        Sync {
+            server: String,
+            #[source]
+            source: std::io::Error,
+        },
+        #[error("locked out after {_0} tries")]
+        LockedOut(u8),
+        #[error("rate limited")]
+        RateLimited,
+    }
+
+    #[derive(Debug, Error)]
+    #[error(serialize)]
+    enum BatchError {
+        #[error("the login failed")]
+        Login(#[from] LoginError),
+        #[error(many)]
+        Many(MultiError<LoginError>),
+    }
+
+    fn to_json<T: serde::Serialize>(value: &T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[test]
+    fn named_fields() {
+        let err = LoginError::UnknownUser {
+            user: String::from("barrett"),
+            attempts: 2,
+        };
+
+        assert_eq!(
+            to_json(&err),
+            json!({
+                "kind": "UnknownUser",
+                "message": "no user named `barrett`",
+                "fields": { "user": "barrett", "attempts": 2 },
+                "source": null,
+            })
+        );
+    }
+
+    #[test]
+    fn skipped_fields() {
+        let err = LoginError::WrongPassword {
+            user: String::from("barrett"),
+            password: String::from("hunter2"),
+        };
+
+        let json = to_json(&err);
+        assert_eq!(json["fields"], json!({ "user": "barrett" }));
+        assert!(!json.to_string().contains("hunter2"));
+    }
+
+    #[test]
+    fn unnamed_and_unit_fields() {
+        assert_eq!(
+            to_json(&LoginError::LockedOut(3))["fields"],
+            json!({ "0": 3 })
+        );
+        assert_eq!(to_json(&LoginError::RateLimited)["fields"], json!({}));
+    }
+
+    #[test]
+    fn sources_use_their_messages() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "users.db is missing");
+        let err = BatchError::from(LoginError::from(io));
+        assert!(err.source().is_some());
+
+        assert_eq!(
+            to_json(&err),
+            json!({
+                "kind": "Login",
+                "message": "the login failed",
+                "fields": {},
+                "source": {
+                    "message": "the user database couldn't be read",
+                    "source": {
+                        "message": "users.db is missing",
+                        "source": null,
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn source_fields_are_only_the_source() {
+        let err = LoginError::Sync {
+            server: String::from("ldap.example.com"),
+            source: std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out"),
+        };
+
+        assert_eq!(
+            to_json(&err),
+            json!({
+                "kind": "Sync",
+                "message": "couldn't sync with `ldap.example.com`",
+                "fields": { "server": "ldap.example.com" },
+                "source": { "message": "timed out", "source": null },
+            })
+        );
+    }
+
+    #[test]
+    fn many_fields_use_their_messages() {
+        let errors = [LoginError::RateLimited, LoginError::LockedOut(5)];
+        let err = BatchError::Many(errors.into_iter().collect());
+
+        assert_eq!(
+            to_json(&err)["fields"],
+            json!({ "0": ["rate limited", "locked out after 5 tries"] })
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Checks the enums and conversions made by `error_set!`.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+mod tests {
+    use pisserror::error_set;
+    use std::error::Error;
+
+    error_set! {
+        /// Something was wrong with the input.
+        #[derive(Debug)]
+        pub ParseError = {
+            #[error("unexpected end of input")]
+            Eof,
+            #[error("bad token `{_0}` at {_1}")]
+            BadToken(char, usize),
+            #[error("`{name}` isn't a known key")]
+            UnknownKey { name: String },
+        };
+
+        #[derive(Debug)]
+        ReadError = {
+            #[error(transparent)]
+            Io(#[from] std::io::Error),
+        };
+
+        /// Loading a config failed.
+        #[derive(Debug)]
+        #[error(status = 500)]
+        pub LoadError = ParseError || ReadError || {
+            #[error("the config is empty")]
+            #[error(status = 422)]
+            Empty,
+        };
+    }
+
+    #[test]
+    fn supersets_flatten_subsets() {
+        let err = LoadError::from(ParseError::BadToken('%', 12));
+        assert!(matches!(err, LoadError::BadToken('%', 12)));
+        assert_eq!(err.to_string(), "bad token `%` at 12");
+
+        let err = LoadError::from(ParseError::UnknownKey {
+            name: "colour".into(),
+        });
+        assert_eq!(err.to_string(), "`colour` isn't a known key");
+
+        // `#[from]` fields come along, too
+        let err = LoadError::from(std::io::Error::other("disk on fire"));
+        assert!(err.source().is_some());
+        let read = LoadError::from(ReadError::from(std::io::Error::other("oops")));
+        assert!(matches!(read, LoadError::Io(_)));
+    }
+
+    #[test]
+    fn subsets_come_back_out() {
+        let parse = ParseError::try_from(LoadError::Eof).unwrap();
+        assert!(matches!(parse, ParseError::Eof));
+
+        let named = ParseError::try_from(LoadError::UnknownKey { name: "a".into() }).unwrap();
+        assert!(matches!(named, ParseError::UnknownKey { ref name } if name == "a"));
+
+        // variants that aren't in the subset are given back
+        let not_parse = ParseError::try_from(LoadError::Empty).unwrap_err();
+        assert!(matches!(not_parse, LoadError::Empty));
+        assert!(ReadError::try_from(LoadError::Eof).is_err());
+    }
+
+    #[test]
+    fn sets_keep_their_own_options() {
+        assert_eq!(LoadError::Empty.to_problem_details().status, Some(422));
+        assert_eq!(LoadError::Eof.to_problem_details().status, Some(500));
+    }
+}
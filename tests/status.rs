@@ -0,0 +1,80 @@
+//! Checks the `HttpStatus` implementations made by the `status` option.
+
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+mod tests {
+    use pisserror::{Error, HttpStatus};
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    enum AuthError {
+        #[error("the token expired")]
+        #[error(status = 401)]
+        Expired,
+        #[error("you can't do that")]
+        #[error(status = 403)]
+        Forbidden,
+    }
+
+    #[derive(Debug, Error)]
+    #[error(status = 502)]
+    enum ApiError {
+        #[error(transparent)]
+        Auth(#[from] AuthError),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("no user named `{_0}`")]
+        #[error(status = 404)]
+        NoUser(String),
+        #[error("the upstream server hung up")]
+        Upstream,
+    }
+
+    #[derive(Debug, Error)]
+    enum NoDefault {
+        #[error("too many requests")]
+        #[error(status = 429)]
+        RateLimited,
+        #[error(transparent)]
+        Api(#[from] ApiError),
+        #[error("oops")]
+        Oops,
+    }
+
+    #[test]
+    fn variant_status() {
+        assert_eq!(ApiError::NoUser(String::from("barrett")).status_code(), 404);
+        assert_eq!(NoDefault::RateLimited.status_code(), 429);
+    }
+
+    #[test]
+    fn enum_default() {
+        assert_eq!(ApiError::Upstream.status_code(), 502);
+        assert_eq!(NoDefault::Oops.status_code(), 500);
+    }
+
+    #[test]
+    fn transparent_variants_delegate() {
+        assert_eq!(ApiError::from(AuthError::Expired).status_code(), 401);
+        assert_eq!(ApiError::from(AuthError::Forbidden).status_code(), 403);
+
+        // through two layers
+        let err = NoDefault::from(ApiError::from(AuthError::Forbidden));
+        assert_eq!(err.status_code(), 403);
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn transparent_variants_without_the_trait_use_the_default() {
+        let err = ApiError::from(std::io::Error::other("connection reset"));
+        assert_eq!(err.status_code(), 502);
+    }
+
+    #[test]
+    fn works_as_a_trait_object() {
+        let errors: [&dyn HttpStatus; 2] = [&AuthError::Expired, &ApiError::Upstream];
+        let codes: Vec<u16> = errors.iter().map(|e| e.status_code()).collect();
+        assert_eq!(codes, [401, 502]);
+    }
+}
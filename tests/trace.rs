@@ -0,0 +1,184 @@
+//! Checks the `Trace` implementations made by `#[error(trace)]`, along
+//! with `ResultExt::trace_err`.
+
+#![cfg(feature = "tracing")]
+
+extern crate alloc;
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+    use alloc::sync::Arc;
+    use core::fmt::Debug;
+    use std::sync::Mutex;
+
+    use pisserror::{Error, ResultExt as _, Trace};
+    use std::error::Error;
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Level, Metadata, Subscriber,
+    };
+
+    /// An event that was recorded, with its fields in order.
+    #[derive(Debug)]
+    struct Recorded {
+        level: Level,
+        fields: Vec<(String, String)>,
+    }
+
+    /// A subscriber that holds onto every event it sees.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<Recorded>>>);
+
+    impl Visit for Recorded {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.fields
+                .push((field.name().to_owned(), format!("{value:?}")));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields
+                .push((field.name().to_owned(), value.to_owned()));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut recorded = Recorded {
+                level: *event.metadata().level(),
+                fields: Vec::new(),
+            };
+            event.record(&mut recorded);
+            self.0.lock().unwrap().push(recorded);
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    /// Runs `f` with a fresh recorder, returning the events it saw.
+    fn record<F: FnOnce()>(f: F) -> Vec<Recorded> {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), f);
+        let events = recorder.0.lock().unwrap().drain(..).collect();
+        events
+    }
+
+    fn field<'rec>(event: &'rec Recorded, name: &str) -> Option<&'rec str> {
+        event
+            .fields
+            .iter()
+            .find(|field| field.0 == name)
+            .map(|field| field.1.as_str())
+    }
+
+    #[derive(Debug, Error)]
+    #[error(trace)]
+    enum LoginError {
+        #[error("wrong password for `{user}`")]
+        WrongPassword {
+            user: String,
+            #[error(skip_serializing)]
+            password: String,
+        },
+        #[error("couldn't reach the database")]
+        Database(#[from] std::io::Error),
+        #[error("logins are disabled")]
+        #[error(level = warn)]
+        Disabled,
+    }
+
+    #[derive(Debug, Error)]
+    #[error(trace)]
+    enum FfiError {
+        #[error("an argument was invalid")]
+        #[error(errno = -22)]
+        InvalidArgument,
+    }
+
+    #[test]
+    fn records_kind_message_and_fields() {
+        let events = record(|| {
+            LoginError::WrongPassword {
+                user: String::from("barrett"),
+                password: String::from("hunter2"),
+            }
+            .trace(Level::WARN);
+        });
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.level, Level::WARN);
+        assert_eq!(field(event, "error.kind"), Some("WrongPassword"));
+        assert_eq!(
+            field(event, "error.message"),
+            Some("wrong password for `barrett`")
+        );
+        assert_eq!(field(event, "user"), Some("\"barrett\""));
+
+        // skipped fields stay secret
+        assert_eq!(field(event, "password"), None);
+        assert!(!event.fields.iter().any(|field| field.1.contains("hunter2")));
+    }
+
+    #[test]
+    fn records_unnamed_and_unit_variants() {
+        let events = record(|| {
+            let err = LoginError::from(std::io::Error::other("connection reset"));
+            assert!(err.source().is_some());
+            err.trace(Level::DEBUG);
+            LoginError::Disabled.trace(Level::INFO);
+        });
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].level, Level::DEBUG);
+        assert!(field(&events[0], "_0")
+            .unwrap()
+            .contains("connection reset"));
+        assert_eq!(events[1].level, Level::INFO);
+        assert_eq!(field(&events[1], "error.kind"), Some("Disabled"));
+        assert_eq!(field(&events[1], "error.code"), None);
+    }
+
+    #[test]
+    fn records_raw_codes() {
+        let events = record(|| FfiError::InvalidArgument.trace(Level::TRACE));
+        assert_eq!(field(&events[0], "error.code"), Some("-22"));
+    }
+
+    #[test]
+    fn trace_err_records_errors_only() {
+        let events = record(|| {
+            let ok: Result<(), LoginError> = Ok(());
+            assert!(ok.trace_err().is_ok());
+
+            let err: Result<(), LoginError> = Err(LoginError::Disabled);
+            assert!(matches!(err.trace_err(), Err(LoginError::Disabled)));
+
+            let io: Result<(), LoginError> = Err(std::io::Error::other("oops").into());
+            assert!(io.trace_err().is_err());
+        });
+
+        // each event uses the error's level
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].level, Level::WARN);
+        assert_eq!(
+            field(&events[0], "error.message"),
+            Some("logins are disabled")
+        );
+        assert_eq!(events[1].level, Level::ERROR);
+    }
+}
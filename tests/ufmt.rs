@@ -0,0 +1,76 @@
+//! Checks the `ufmt` implementations made by `#[error(ufmt)]`.
+
+#![cfg(feature = "ufmt")]
+
+#[cfg(test)]
+mod tests {
+    use pisserror::Error;
+    use std::error::Error;
+
+    #[derive(Debug, Error)]
+    #[error(ufmt)]
+    enum SensorError {
+        #[error("sensor {id} timed out after {ms}ms")]
+        Timeout { id: u8, ms: u32 },
+        #[error("bad register {{{:#04x}}}: {:?}", register, value)]
+        BadRegister { register: u8, value: u16 },
+        #[error("the bus is busy")]
+        Busy,
+    }
+
+    #[derive(Debug, Error)]
+    #[error(ufmt)]
+    enum BoardError {
+        #[error(transparent)]
+        Sensor(#[from] SensorError),
+        #[error("status {_0:X}")]
+        Status(u16),
+        #[error(many)]
+        Many(Vec<SensorError>),
+    }
+
+    /// Formats with `ufmt`, then checks it against `Display`.
+    fn ufmt_string<T: ufmt::uDisplay + ufmt::uDebug + core::fmt::Display>(err: &T) -> String {
+        let mut displayed = String::new();
+        ufmt::uwrite!(displayed, "{}", err).unwrap();
+
+        let mut debugged = String::new();
+        ufmt::uwrite!(debugged, "{:?}", err).unwrap();
+
+        assert_eq!(displayed, err.to_string());
+        assert_eq!(displayed, debugged);
+        displayed
+    }
+
+    #[test]
+    fn messages_match_display() {
+        assert_eq!(
+            ufmt_string(&SensorError::Timeout { id: 3, ms: 250 }),
+            "sensor 3 timed out after 250ms"
+        );
+        assert_eq!(
+            ufmt_string(&SensorError::BadRegister {
+                register: 10,
+                value: 2
+            }),
+            "bad register {0x0a}: 2"
+        );
+        assert_eq!(ufmt_string(&BoardError::Status(0xbeef)), "status BEEF");
+    }
+
+    #[test]
+    fn transparent_and_many() {
+        let err = BoardError::from(SensorError::Busy);
+        assert!(err.source().is_some());
+        assert_eq!(ufmt_string(&err), "the bus is busy");
+
+        let many = BoardError::Many(vec![
+            SensorError::Busy,
+            SensorError::Timeout { id: 1, ms: 5 },
+        ]);
+        assert_eq!(
+            ufmt_string(&many),
+            "1. the bus is busy\n2. sensor 1 timed out after 5ms"
+        );
+    }
+}